@@ -1,26 +1,67 @@
-#![allow(static_mut_refs)]
-
 use log::debug;
 use pyo3::prelude::*;
-use pyo3::{exceptions::{PyRuntimeError, PyOSError}, types::{PyAny, PyDict, PyInt, PyBool, PyString}};
+use pyo3::call::PyCallArgs;
+use pyo3::buffer::PyBuffer;
+use pyo3::{exceptions::{PyRuntimeError, PyOSError, PyTypeError, PyValueError}, types::{PyAny, PyDict, PyInt, PyBool, PyString, PyBytes, PyList}};
 use eframe::{egui, self};
 use eframe::egui::{FontData, FontDefinitions, FontFamily};
-use egui_extras;
 use std::sync::{Mutex, Arc};
-use std::{ptr, fs};
-use chrono::NaiveDate;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering};
+use std::collections::HashMap;
+use std::cell::RefCell;
+use std::fs;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+#[cfg(feature = "testing")]
+use egui_kittest::kittest::Queryable;
+#[cfg(feature = "testing")]
+use pyo3::exceptions::{PyAssertionError, PyLookupError};
 
 // state
 
-static mut UI: *mut Vec<*mut egui::Ui> = ptr::null_mut();
-static mut APP_MUTEX: Mutex<()> = Mutex::new(());
+thread_local! {
+  // Ui of update_func and of the nested functions being executed, the innermost is the last.
+  // The pointers are valid only while the function that pushed them runs, see UiGuard.
+  // A null pointer blocks UI functions in callbacks run by egui while it uses the Ui, see block_ui
+  static UI_STACK: RefCell<Vec<*mut egui::Ui>> = const { RefCell::new(Vec::new()) };
+  // whether a submenu button was clicked in each menu being drawn, the innermost is the last
+  static SUBMENU_CLICKED: RefCell<Vec<bool>> = const { RefCell::new(Vec::new()) };
+  // the first exception raised by a formatter of the widget being drawn, see with_formatter_error
+  static FORMATTER_ERROR: RefCell<Option<PyErr>> = const { RefCell::new(None) };
+}
+static APP_MUTEX: Mutex<()> = Mutex::new(());
+static TOASTS: Mutex<Toasts> = Mutex::new(Toasts { queue: Vec::new() });
+// lets other threads wake the app up
+static APP_CTX: Mutex<Option<egui::Context>> = Mutex::new(None);
+static ERROR_POLICY: Mutex<ErrorPolicy> = Mutex::new(ErrorPolicy::Raise);
+// exception that closed the app, re-raised by run_native
+static APP_ERROR: Mutex<Option<PyErr>> = Mutex::new(None);
+static ERROR_HANDLER: Mutex<Option<Py<PyAny>>> = Mutex::new(None);
+// traceback shown in the error panel
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+// False turns AccessKit off even when a screen reader asks for it
+static ACCESSIBILITY: AtomicBool = AtomicBool::new(true);
+// fonts of add_font and set_font_family, None means the default fonts
+static FONTS: Mutex<Option<FontDefinitions>> = Mutex::new(None);
+static FONTS_CHANGED: AtomicBool = AtomicBool::new(false);
+// the next id stored on Matplotlib figures as _pyegui_id
+static NEXT_FIGURE_ID: AtomicU64 = AtomicU64::new(0);
 
 // messages
 
-static APP_MUTEX_ERR: &'static str = "run_simple_native has been called on a separate thread";
-static UI_PTR_NULL_ERR: &'static str = "UI ptr is null. This is likely to be a problem with pyegui";
-static UI_STACK_ERR: &'static str = "UI stack is empty. This is likely to be a problem with pyegui";
-static UI_CALL_OUTSIDE_UPDATE_FUNC: &'static str = "UI functions should be called only within update_fun and on the same thread. update_fun should only be called by run_simple_native";
+static APP_MUTEX_ERR: &str = "run_native is already running, only one app can run at a time";
+static UI_CALL_OUTSIDE_UPDATE_FUNC: &str = "UI functions should be called only within update_func and on the thread that called run_native";
+static UI_CALL_IN_CALLBACK: &str = "UI functions can't be called inside formatters, parsers and layouters";
+static COLOR_ERR: &str = "Color should be Color, RGB, RGBA, HSVA, a hex string or a tuple of 3 or 4 integers in the 0-255 range";
+static SRGB_LEN_ERR: &str = "Color should be a list of 3 integers in the 0-255 range";
+static IMAGE_SHAPE_ERR: &str = "Image should be a PIL image or a uint8 array with (H, W, 3) or (H, W, 4) shape";
+static TEXTURE_UPDATE_ERR: &str = "Updated part should fit in the texture";
+static POINTS_SHAPE_ERR: &str = "Points array should have (N, 2) shape";
+static VALUES_SHAPE_ERR: &str = "Values array should be one-dimensional";
+static STATE_STORAGE_KEY: &str = "pyegui_state";
+static CONTINUOUS_KEY: &str = "pyegui_continuous";
+static FIGURES_KEY: &str = "pyegui_figures";
+static CONTAINER_ERR: &str = "Container should be used in a with statement and can't be entered twice";
+static PLOT_ITEM_ERR: &str = "Plot items should be Line, Points or BarChart objects";
 
 // classes
 
@@ -66,11 +107,98 @@ impl Context {
         self.0.set_theme(egui::ThemePreference::System);        
     }
 
+    /// Sets the theme: "dark", "light" or "system". Default is "system"
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if button_clicked("Toggle theme"):
+    ///       ctx.set_theme("light" if ctx.theme == "dark" else "dark")
+    fn set_theme(&self, theme: &str) -> PyResult<()> {
+        let preference = match theme {
+          "dark" => egui::ThemePreference::Dark,
+          "light" => egui::ThemePreference::Light,
+          "system" => egui::ThemePreference::System,
+          other => return Err(PyValueError::new_err(format!("Unknown theme '{}', expected 'dark', 'light' or 'system'", other)))
+        };
+
+        self.0.set_theme(preference);
+        Ok(())
+    }
+
+    /// Copy of the style of the current theme. Change it and apply it with set_style
+    fn style(&self) -> Style {
+        Style((*self.0.style()).clone())
+    }
+
+    /// Use the style. The change is visible from the next frame.
+    ///
+    /// Args:
+    ///     style (Style): the style
+    ///
+    ///     theme (str | None): "dark" or "light" to set the style of that theme. Default is the current theme
+    #[pyo3(signature = (style, theme = None))]
+    fn set_style(&self, style: &Style, theme: Option<&str>) -> PyResult<()> {
+        match theme {
+          None => self.0.set_style(style.0.clone()),
+          Some("dark") => self.0.set_style_of(egui::Theme::Dark, style.0.clone()),
+          Some("light") => self.0.set_style_of(egui::Theme::Light, style.0.clone()),
+          Some(other) => return Err(PyValueError::new_err(format!("Unknown theme '{}', expected 'dark' or 'light'", other)))
+        }
+        Ok(())
+    }
+
+    /// Current theme: "dark" or "light". With the "system" preference it is the system's theme
+    #[getter]
+    fn theme(&self) -> &'static str {
+        match self.0.theme() {
+          egui::Theme::Dark => "dark",
+          egui::Theme::Light => "light",
+        }
+    }
+
+    /// Scale the whole UI, e.g. 1.5 makes everything 50% bigger. Default is 1.0.
+    ///
+    /// The change is visible from the next frame. Users can also zoom with Ctrl+Plus, Ctrl+Minus and Ctrl+0,
+    /// see set_zoom_with_keyboard.
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if slider_float(zoom, 0.5, 3.0, "Zoom").changed():
+    ///       ctx.set_zoom_factor(zoom.value)
+    fn set_zoom_factor(&self, zoom_factor: f32) -> PyResult<()> {
+        if !(zoom_factor.is_finite() && zoom_factor > 0.0) {
+          return Err(PyValueError::new_err("Zoom factor must be a positive finite number"));
+        }
+
+        self.0.set_zoom_factor(zoom_factor);
+        Ok(())
+    }
+
+    /// Current zoom factor of the UI
+    #[getter]
+    fn zoom_factor(&self) -> f32 {
+        self.0.zoom_factor()
+    }
+
+    /// Allow zooming with Ctrl+Plus, Ctrl+Minus and Ctrl+0 (Cmd on Mac). It is allowed by default
+    fn set_zoom_with_keyboard(&self, enabled: bool) {
+        self.0.options_mut(|o| o.zoom_with_keyboard = enabled);
+    }
+
+    /// True if the UI can be zoomed with the keyboard
+    #[getter]
+    fn zoom_with_keyboard(&self) -> bool {
+        self.0.options(|o| o.zoom_with_keyboard)
+    }
+
     /// Tell egui which fonts to use.
     ///
     /// The default egui fonts only support latin and cyrillic alphabets, but you can call this to install additional fonts that support e.g. Japanese characters.
     ///
     /// The new fonts will become active at the start of the next pass. This will overwrite the existing fonts.
+    /// Use add_font to load several fonts.
     ///  
     /// Example::
     /// 
@@ -78,9 +206,8 @@ impl Context {
     ///     ctx.set_font("NotoSansJP-VariableFont_wght.ttf")
     ///     heading("天気の子")
     fn set_font(&self, source: String) -> PyResult<()> {
-      let buf = fs::read(&source).map_err(|e| {
-        eprintln!("Cannot open '{}': {}", source, e.to_string());
-        e
+      let buf = fs::read(&source).inspect_err(|e| {
+        eprintln!("Cannot open '{}': {}", source, e);
       })?;
       
       let mut fonts = FontDefinitions::default();
@@ -107,963 +234,7270 @@ impl Context {
     }
 
     /// Copy the given text to the system clipboard.
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     label(token)
+    ///     if button_clicked("Copy to clipboard"):
+    ///       ctx.copy_text(token)
     fn copy_text(&self, text: String) {
         self.0.copy_text(text);
     }
-}
-
-/// Str stores string value that can be referenced
-///
-/// Usage::
-///
-///     data = Str("")
-///     
-///     def update_func():
-///         heading(f"Value of the data is {data.value}")
-///         if button_clicked("Add :)"):
-///             data.value += ":) "
-#[pyclass]
-struct Str {
-    #[pyo3(get, set)]
-    value: String
-}
 
-#[pymethods]
-impl Str {
-    #[new]
-    fn new(value: String) -> Self {
-        Str { value }
+    /// Release the image loaded from the given uri, so that it is loaded again next time it is shown.
+    ///
+    /// Example::
+    ///
+    ///     if button_clicked("Refresh"):
+    ///         ctx.forget_image("https://example.com/latest.png")
+    ///     image("https://example.com/latest.png")
+    fn forget_image(&self, uri: &str) {
+        self.0.forget_image(uri);
     }
-}
 
+    /// Release all loaded images, including the ones shown with image_from_bytes.
+    fn forget_all_images(&self) {
+        self.0.forget_all_images();
+    }
 
-/// Bool stores a boolean value that can be referenced
-///
-/// Usage::
-///
-///     data = Bool(False)
-///     
-///     def update_func():
-///         heading(f"Value of the data is {data.value}")
-///         # button will be shown only if the checkbox is checked 
-///         if data.value and button_clicked("set to False"):
-///             # hiding the button
-///             data.value = False
-///         checkbox(data, "Check me")
-#[pyclass]
-struct Bool {
-    #[pyo3(get, set)]
-    value: bool
-}
+    /// Enable or disable fullscreen mode of the window.
+    fn set_fullscreen(&self, fullscreen: bool) {
+        self.0.send_viewport_cmd(egui::ViewportCommand::Fullscreen(fullscreen));
+    }
 
-#[pymethods]
-impl Bool {
-    #[new]
-    fn new(value: bool) -> Self {
-        Bool { value }
+    /// Maximize or restore the window.
+    fn set_maximized(&self, maximized: bool) {
+        self.0.send_viewport_cmd(egui::ViewportCommand::Maximized(maximized));
     }
-}
 
-/// Int stores integer value that can be referenced
-///
-/// Usage::
-///
-///     data = Int(69)
-///     
-///     def update_func():
-///         heading(f"Value of the data is {data.value}")
-///         if button_clicked("Increment"):
-///             data.value += 1
-#[pyclass]
-struct Int {
-    #[pyo3(get, set)]
-    value: i32
-}
+    /// Minimize the window.
+    fn minimize(&self) {
+        self.0.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+    }
 
-#[pymethods]
-impl Int {
-    #[new]
-    fn new(value: i32) -> Self {
-        Int { value }
+    /// Change the title of the window.
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     ctx.set_window_title(f"{path.value}{'*' if dirty.value else ''} - Editor")
+    fn set_window_title(&self, text: String) {
+        self.0.send_viewport_cmd(egui::ViewportCommand::Title(text));
     }
-}
 
-/// Float stores float value that can be referenced
-///
-/// Usage::
-///
-///     data = Float(69.0)
-///     
-///     def update_func():
-///         heading(f"Value of the data is {data.value}")
-///         if button_clicked("Increment"):
-///             # what can go wrong?
-///             data.value += 0.1
-#[pyclass]
-struct Float {
-    #[pyo3(get, set)]
-    value: f32
-}
+    /// Capture the window. callback is called with the image a few frames later.
+    ///
+    /// Args:
+    ///     callback (Callable[[bytes | numpy.ndarray], None]): your function that receives the image
+    ///
+    ///     format (str): "png" passes png bytes, "array" passes a uint8 numpy array with (H, W, 4) shape. Default is "png"
+    ///
+    /// Example::
+    ///
+    ///   def save(png):
+    ///     with open("view.png", "wb") as f:
+    ///       f.write(png)
+    ///
+    ///   def update_func(ctx):
+    ///     if button_clicked("Export view"):
+    ///       ctx.screenshot(save)
+    #[pyo3(signature = (callback, format = "png"))]
+    fn screenshot(&self, callback: Py<PyAny>, format: &str) -> PyResult<()> {
+        let png = match format {
+          "png" => true,
+          "array" => false,
+          other => return Err(PyValueError::new_err(format!("Unknown screenshot format '{}'", other)))
+        };
+
+        let request = ScreenshotRequest { callback, png };
+        self.0.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::new(request)));
+        Ok(())
+    }
 
-#[pymethods]
-impl Float {
-    #[new]
-    fn new(value: f32) -> Self {
-        Float { value }
+    /// Returns a RepaintHandle that background threads can use to wake the app up.
+    fn repaint_handle(&self) -> RepaintHandle {
+        RepaintHandle(self.0.clone())
     }
-}
 
+    /// Switch between continuous and reactive mode.
+    ///
+    /// In continuous mode update_func is called every frame, which suits animations and simulations.
+    /// In reactive mode, the default one, it is called only when something happens, e.g. the mouse moves.
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     ctx.set_continuous(simulation_running.value)
+    fn set_continuous(&self, continuous: bool) {
+        self.0.data_mut(|d| d.insert_temp(egui::Id::new(CONTINUOUS_KEY), continuous));
+    }
 
-/// Rgb color picker
-///
-/// Usage::
-///
-///     color_rgb = RGB(69, 69, 69)
-///     color_edit_button_rgb(color_rgb)
-#[pyclass]
-struct RGB {
-  #[pyo3(get, set)]
-  r: f32,
-  #[pyo3(get, set)]
-  g: f32,
-  #[pyo3(get, set)]
-  b: f32,
-}
+    /// True if the app is in continuous mode
+    #[getter]
+    fn is_continuous(&self) -> bool {
+        self.0.data(|d| d.get_temp(egui::Id::new(CONTINUOUS_KEY)).unwrap_or(false))
+    }
 
-#[pymethods]
-impl RGB {
-    #[new]
-    fn new(r: f32, g: f32, b: f32) -> Self {
-        RGB { r, g, b }
+    /// Animate a bool: returns a value going smoothly from 0.0 to 1.0 when value becomes True, and back when it becomes False.
+    ///
+    /// The first call returns 0.0 or 1.0 without an animation. The app is repainted until the animation ends.
+    ///
+    /// Args:
+    ///     id (str): unique id of the animation
+    ///
+    ///     value (bool): the target state
+    ///
+    ///     time (float | None): duration of the animation in seconds. Default is the animation time of the style
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     checkbox(show_details, "Details")
+    ///     opacity = ctx.animate_bool("details", show_details.value)
+    ///     if opacity > 0.0:
+    ///       label("Details", color=Color(255, 255, 255, int(opacity * 255)))
+    #[pyo3(signature = (id, value, time = None))]
+    fn animate_bool(&self, id: &str, value: bool, time: Option<f32>) -> f32 {
+        match time {
+          Some(time) => self.0.animate_bool_with_time(egui::Id::new(id), value, time),
+          None => self.0.animate_bool(egui::Id::new(id), value),
+        }
     }
-}
 
-/// Date picker
-///
-/// Usage::
-///
-///     date = Date(datetime.datetime.now())
-///     date_picker_button(date)
-#[pyclass]
-struct Date {
-    #[pyo3(get, set)]
-    value: NaiveDate
-}
+    /// Animate a float: returns a value moving smoothly to value in time seconds whenever value changes.
+    ///
+    /// The first call returns value without an animation. The app is repainted until the animation ends.
+    ///
+    /// Args:
+    ///     id (str): unique id of the animation
+    ///
+    ///     value (float): the target value
+    ///
+    ///     time (float): duration of the animation in seconds
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     slider_float(target, 0, 300, "Position")
+    ///     x = ctx.animate_value_with_time("marker", target.value, 0.3)
+    ///     with horizontal():
+    ///       add_space(x)
+    ///       label("▲")
+    fn animate_value_with_time(&self, id: &str, value: f32, time: f32) -> f32 {
+        self.0.animate_value_with_time(egui::Id::new(id), value, time)
+    }
 
-#[pymethods]
-impl Date {
-    #[new]
-    fn new(value: NaiveDate) -> Self {
-        Date { value }
+    /// Move a value smoothly to target with an easing. Returns the current value, call it every frame.
+    ///
+    /// When target changes, the value moves from where it is now to the new target in duration seconds.
+    /// The first call returns target without an animation. The app is repainted until the animation ends.
+    ///
+    /// Args:
+    ///     id (str): unique id of the animation
+    ///
+    ///     target (float): the value to move to
+    ///
+    ///     duration (float): duration of the animation in seconds
+    ///
+    ///     easing (str | Callable[[float], float]): "linear", "quadratic_in", "quadratic_out", "quadratic_in_out",
+    ///       "cubic_*", "sin_*", "circular_*", "exponential_*", "back_*", "bounce_*" or your function that maps
+    ///       the progress from 0.0-1.0 to 0.0-1.0. Default is "cubic_in_out"
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if button_clicked("Toggle"):
+    ///       expanded.value = not expanded.value
+    ///     height = ctx.tween("box", 200 if expanded.value else 40, 0.4, easing="back_out")
+    ///     allocate_response((200, height))
+    #[pyo3(signature = (id, target, duration, easing = None))]
+    fn tween(&self, id: &str, target: f32, duration: f32, easing: Option<&Bound<'_, PyAny>>) -> PyResult<f32> {
+        let easing = Easing::extract(easing)?;
+        let id = egui::Id::new(id).with("pyegui_tween");
+        let now = self.0.input(|i| i.time);
+
+        let mut state = self.0.data(|d| d.get_temp::<Tween>(id))
+          .unwrap_or(Tween { from: target, to: target, started_at: now });
+
+        if state.to != target {
+          let value = state.value(now, duration, &easing)?;
+          state = Tween { from: value, to: target, started_at: now };
+        }
+        self.0.data_mut(|d| d.insert_temp(id, state));
+
+        if state.from != state.to && now - state.started_at < duration as f64 {
+          self.0.request_repaint();
+        }
+
+        state.value(now, duration, &easing)
     }
-}
 
-// Start function
+    /// Send a command to the window.
+    ///
+    /// Example::
+    ///
+    ///   if button_clicked("Bigger"):
+    ///     ctx.send_viewport_cmd(ViewportCommand.inner_size(1024, 768))
+    fn send_viewport_cmd(&self, cmd: &ViewportCommand) {
+        self.0.send_viewport_cmd(cmd.0.clone());
+    }
 
-struct PyeguiApp<'py> {
-    update_func: Bound<'py, PyAny>
-}
+    /// True if the key was pressed this frame. key can be Key or its name, e.g. "Enter" or "A".
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if ctx.key_pressed(Key.Escape):
+    ///       ctx.send_viewport_cmd(ViewportCommand.close())
+    fn key_pressed(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let key = extract_key(key)?;
+        Ok(self.0.input(|i| i.key_pressed(key)))
+    }
 
-impl eframe::App for PyeguiApp<'_> {
-  fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    /// True while the key is held down
+    fn key_down(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let key = extract_key(key)?;
+        Ok(self.0.input(|i| i.key_down(key)))
+    }
 
-    let ctx_r = Context(ctx.clone());
+    /// True if the key was released this frame
+    fn key_released(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let key = extract_key(key)?;
+        Ok(self.0.input(|i| i.key_released(key)))
+    }
 
-    unsafe {
+    /// True if the shortcut was pressed this frame, e.g. "Ctrl+S" or "Ctrl+Shift+Z".
+    ///
+    /// The key press is consumed, so it isn't typed into a focused text field or handled by another shortcut.
+    ///
+    /// Modifiers are "Ctrl", "Shift", "Alt", "MacCmd" and "Cmd". "Cmd" means command on Mac and ctrl on other platforms.
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if ctx.shortcut_pressed("Cmd+S"):
+    ///       save()
+    fn shortcut_pressed(&self, shortcut: &str) -> PyResult<bool> {
+        let shortcut = parse_shortcut(shortcut)?;
+        Ok(self.0.input_mut(|i| i.consume_shortcut(&shortcut)))
+    }
 
-      egui::CentralPanel::default().show(ctx, |ui| {
+    /// Text typed this frame, including text committed by an input method (IME).
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     for text in ctx.text_events():
+    ///       terminal.write(text)
+    fn text_events(&self) -> Vec<String> {
+        self.0.input(|i| i.events.iter().filter_map(|e| match e {
+          egui::Event::Text(text) => Some(text.clone()),
+          egui::Event::Ime(egui::ImeEvent::Commit(text)) => Some(text.clone()),
+          _ => None
+        }).collect())
+    }
 
-        debug!("Getting ui stack pointer");
-        let ui_stack = UI.as_mut().expect(UI_PTR_NULL_ERR);
+    /// Input method (IME) events of this frame as (kind, text) tuples, used to type e.g. Japanese or Chinese.
+    ///
+    /// Kinds are "enabled", "preedit" (text being composed), "commit" (final text) and "disabled".
+    /// The text is empty for "enabled" and "disabled". Text fields handle these events themselves,
+    /// use this for custom text widgets together with request_ime.
+    ///
+    /// The default fonts have no CJK glyphs, so set a font that has them with set_font.
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     for kind, text in ctx.ime_events():
+    ///       if kind == "preedit":
+    ///         editor.composing = text
+    ///       elif kind == "commit":
+    ///         editor.insert(text)
+    ///         editor.composing = ""
+    fn ime_events(&self) -> Vec<(&'static str, String)> {
+        self.0.input(|i| i.events.iter().filter_map(|e| match e {
+          egui::Event::Ime(egui::ImeEvent::Enabled) => Some(("enabled", String::new())),
+          egui::Event::Ime(egui::ImeEvent::Preedit(text)) => Some(("preedit", text.clone())),
+          egui::Event::Ime(egui::ImeEvent::Commit(text)) => Some(("commit", text.clone())),
+          egui::Event::Ime(egui::ImeEvent::Disabled) => Some(("disabled", String::new())),
+          _ => None
+        }).collect())
+    }
 
-        debug!("Push UI");
-        ui_stack.push(&raw mut *ui);
+    /// Turn on the input method (IME) for a custom text widget this frame, so ime_events receives its events.
+    /// The operating system shows the candidate window next to cursor_rect.
+    ///
+    /// Call it every frame while your widget has focus. Text fields do this themselves.
+    ///
+    /// Args:
+    ///     rect (Rect): where the widget is
+    ///
+    ///     cursor_rect (Rect | None): where the text cursor is. Default is rect
+    #[pyo3(signature = (rect, cursor_rect = None))]
+    fn request_ime(&self, rect: Rect, cursor_rect: Option<Rect>) {
+        let cursor_rect = cursor_rect.unwrap_or(rect);
+        self.0.output_mut(|o| o.ime = Some(egui::output::IMEOutput { rect: rect.0, cursor_rect: cursor_rect.0 }));
+    }
 
-        debug!("Execute update_func");
+    /// Text pasted from the clipboard this frame
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     for text in ctx.paste_events():
+    ///       rows = [line.split("\t") for line in text.splitlines()]
+    ///       table.extend(rows)
+    fn paste_events(&self) -> Vec<String> {
+        self.0.input(|i| i.events.iter().filter_map(|e| match e {
+          egui::Event::Paste(text) => Some(text.clone()),
+          _ => None
+        }).collect())
+    }
+
+    /// Latest known position of the mouse pointer, or None if it isn't over the window
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     pos = ctx.pointer_pos()
+    ///     if pos and ctx.pointer_primary_down():
+    ///       stroke.append(pos)
+    fn pointer_pos(&self) -> Option<(f32, f32)> {
+        self.0.input(|i| i.pointer.latest_pos()).map(|p| (p.x, p.y))
+    }
+
+    /// True while the primary mouse button is held down
+    fn pointer_primary_down(&self) -> bool {
+        self.0.input(|i| i.pointer.primary_down())
+    }
+
+    /// True if the primary mouse button was clicked this frame
+    fn pointer_clicked(&self) -> bool {
+        self.0.input(|i| i.pointer.primary_clicked())
+    }
+
+    /// How much the pointer moved since the last frame
+    fn pointer_delta(&self) -> (f32, f32) {
+        let delta = self.0.input(|i| i.pointer.delta());
+        (delta.x, delta.y)
+    }
+
+    /// How many points the user scrolled this frame, as reported by the mouse wheel or touchpad
+    fn raw_scroll_delta(&self) -> (f32, f32) {
+        let delta = self.0.input(|i| i.raw_scroll_delta);
+        (delta.x, delta.y)
+    }
+
+    /// Like raw_scroll_delta, but smoothed over several frames. Use it to scroll custom views
+    fn smooth_scroll_delta(&self) -> (f32, f32) {
+        let delta = self.0.input(|i| i.smooth_scroll_delta);
+        (delta.x, delta.y)
+    }
+
+    /// Zoom factor of this frame from pinch gestures or Ctrl+scroll. 1.0 means no zoom
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     global zoom
+    ///     zoom *= ctx.zoom_delta()
+    fn zoom_delta(&self) -> f32 {
+        self.0.input(|i| i.zoom_delta())
+    }
+
+    /// Files dropped onto the window this frame
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     for f in ctx.dropped_files():
+    ///       if f.path:
+    ///         open_document(f.path)
+    fn dropped_files(&self) -> Vec<DroppedFile> {
+        self.0.input(|i| i.raw.dropped_files.iter().cloned().map(DroppedFile).collect())
+    }
+
+    /// Files dragged over the window but not dropped yet
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if ctx.hovered_files():
+    ///       heading("Drop here to import")
+    fn hovered_files(&self) -> Vec<HoveredFile> {
+        self.0.input(|i| i.raw.hovered_files.iter().cloned().map(HoveredFile).collect())
+    }
+
+    /// Modifier keys held down this frame
+    ///
+    /// Example::
+    ///
+    ///   def update_func(ctx):
+    ///     if button("open").clicked():
+    ///       if ctx.modifiers().ctrl:
+    ///         open_in_new_tab()
+    ///       else:
+    ///         open()
+    fn modifiers(&self) -> Modifiers {
+        Modifiers(self.0.input(|i| i.modifiers))
+    }
+}
+
+/// A file dropped onto the window. Get them with Context.dropped_files
+#[pyclass(frozen)]
+struct DroppedFile(egui::DroppedFile);
+
+#[pymethods]
+impl DroppedFile {
+
+    /// Path of the file. None on platforms that don't give access to the file system
+    #[getter]
+    fn path(&self) -> Option<std::path::PathBuf> {
+        self.0.path.clone()
+    }
+
+    /// Name of the file
+    #[getter]
+    fn name(&self) -> String {
+        match &self.0.path {
+          Some(path) if self.0.name.is_empty() => path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+          _ => self.0.name.clone()
+        }
+    }
+
+    /// Mime type of the file if known, otherwise an empty string
+    #[getter]
+    fn mime(&self) -> String {
+        self.0.mime.clone()
+    }
+
+    /// Content of the file if it was read by the platform, e.g. on the web. Otherwise None
+    #[getter]
+    fn bytes<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        self.0.bytes.as_ref().map(|b| PyBytes::new(py, b))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DroppedFile({:?})", self.name())
+    }
+}
+
+/// A file dragged over the window. Get them with Context.hovered_files
+#[pyclass(frozen)]
+struct HoveredFile(egui::HoveredFile);
+
+#[pymethods]
+impl HoveredFile {
+
+    /// Path of the file. None on platforms that don't give access to the file system
+    #[getter]
+    fn path(&self) -> Option<std::path::PathBuf> {
+        self.0.path.clone()
+    }
+
+    /// Mime type of the file if known, otherwise an empty string
+    #[getter]
+    fn mime(&self) -> String {
+        self.0.mime.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("HoveredFile({:?})", self.0.path)
+    }
+}
+
+/// State of the modifier keys. Get it with Context.modifiers()
+#[pyclass(frozen)]
+#[derive(Clone, Copy)]
+struct Modifiers(egui::Modifiers);
+
+#[pymethods]
+impl Modifiers {
+
+    /// Either of the alt keys, or option on Mac
+    #[getter]
+    fn alt(&self) -> bool {
+        self.0.alt
+    }
+
+    /// Either of the ctrl keys
+    #[getter]
+    fn ctrl(&self) -> bool {
+        self.0.ctrl
+    }
+
+    /// Either of the shift keys
+    #[getter]
+    fn shift(&self) -> bool {
+        self.0.shift
+    }
+
+    /// The command key on Mac
+    #[getter]
+    fn mac_cmd(&self) -> bool {
+        self.0.mac_cmd
+    }
+
+    /// The command key on Mac and ctrl on other platforms. Use it for shortcuts like Ctrl+S
+    #[getter]
+    fn command(&self) -> bool {
+        self.0.command
+    }
+
+    /// True if no modifier is held down
+    #[getter]
+    fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+          "Modifiers(alt={}, ctrl={}, shift={}, mac_cmd={}, command={})",
+          self.0.alt, self.0.ctrl, self.0.shift, self.0.mac_cmd, self.0.command
+        )
+    }
+}
+
+/// Wakes the app up from any thread. Get it with Context.repaint_handle
+///
+/// Usage::
+///
+///     result = Str("")
+///
+///     def download(handle):
+///         result.value = requests.get(url).text
+///         handle.request_repaint()
+///
+///     def update_func(ctx):
+///         if button_clicked("Download"):
+///             threading.Thread(target=download, args=(ctx.repaint_handle(),)).start()
+///         label(result.value)
+#[pyclass(frozen)]
+struct RepaintHandle(egui::Context);
+
+#[pymethods]
+impl RepaintHandle {
+
+    /// Call update_func as soon as possible
+    fn request_repaint(&self) {
+        self.0.request_repaint();
+    }
+
+    /// Call update_func after the given number of seconds at the latest
+    fn request_repaint_after(&self, seconds: f64) {
+        self.0.request_repaint_after_secs(seconds as f32);
+    }
+}
+
+/// A key on the keyboard. All keys are available as class attributes: Key.Enter, Key.A, Key.Num1, Key.F5 etc.
+///
+/// Functions that take a Key also accept its name as a string.
+///
+/// Usage::
+///
+///     if ctx.key_pressed(Key.Enter):
+///         send_message()
+///     if ctx.key_down("Space"):
+///         jump()
+#[pyclass(eq, hash, frozen)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Key(egui::Key);
+
+#[pymethods]
+impl Key {
+
+    /// Find a key by its name, e.g. "Enter", "A", "1" or "F5"
+    #[staticmethod]
+    fn from_name(name: &str) -> PyResult<Self> {
+        egui::Key::from_name(name)
+          .map(Key)
+          .ok_or_else(|| PyValueError::new_err(format!("Unknown key '{}'", name)))
+    }
+
+    /// Human readable name of the key
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Key.{:?}", self.0)
+    }
+}
+
+/// ViewportCommand is a command that controls the window. Send it with Context.send_viewport_cmd
+///
+/// Usage::
+///
+///     def update_func(ctx):
+///         if button_clicked("Move"):
+///             ctx.send_viewport_cmd(ViewportCommand.outer_position(0, 0))
+///
+///         if button_clicked("Notify"):
+///             ctx.send_viewport_cmd(ViewportCommand.request_attention())
+#[pyclass]
+#[derive(Clone)]
+struct ViewportCommand(egui::ViewportCommand);
+
+#[pymethods]
+impl ViewportCommand {
+
+    /// Set the size of the window content
+    #[staticmethod]
+    fn inner_size(width: f32, height: f32) -> Self {
+        Self(egui::ViewportCommand::InnerSize(egui::vec2(width, height)))
+    }
+
+    /// Set the min size of the window content
+    #[staticmethod]
+    fn min_inner_size(width: f32, height: f32) -> Self {
+        Self(egui::ViewportCommand::MinInnerSize(egui::vec2(width, height)))
+    }
+
+    /// Set the max size of the window content
+    #[staticmethod]
+    fn max_inner_size(width: f32, height: f32) -> Self {
+        Self(egui::ViewportCommand::MaxInnerSize(egui::vec2(width, height)))
+    }
+
+    /// Move the window to the given position on the screen
+    #[staticmethod]
+    fn outer_position(x: f32, y: f32) -> Self {
+        Self(egui::ViewportCommand::OuterPosition(egui::pos2(x, y)))
+    }
+
+    /// Bring the window into focus
+    #[staticmethod]
+    fn focus() -> Self {
+        Self(egui::ViewportCommand::Focus)
+    }
+
+    /// Ask the user's attention, e.g. by flashing the window in the taskbar.
+    ///
+    /// kind is "informational" (default), "critical" or "reset" to stop requesting attention
+    #[staticmethod]
+    #[pyo3(signature = (kind = "informational"))]
+    fn request_attention(kind: &str) -> PyResult<Self> {
+        let kind = match kind {
+          "informational" => egui::UserAttentionType::Informational,
+          "critical" => egui::UserAttentionType::Critical,
+          "reset" => egui::UserAttentionType::Reset,
+          other => return Err(PyValueError::new_err(format!("Unknown attention type '{}'", other)))
+        };
+
+        Ok(Self(egui::ViewportCommand::RequestUserAttention(kind)))
+    }
+
+    /// Start dragging the window. Should be sent while the mouse button is down, e.g. when a custom title bar is dragged
+    #[staticmethod]
+    fn start_drag() -> Self {
+        Self(egui::ViewportCommand::StartDrag)
+    }
+
+    /// Set the title of the window
+    #[staticmethod]
+    fn title(text: String) -> Self {
+        Self(egui::ViewportCommand::Title(text))
+    }
+
+    /// Enable or disable fullscreen mode
+    #[staticmethod]
+    fn fullscreen(fullscreen: bool) -> Self {
+        Self(egui::ViewportCommand::Fullscreen(fullscreen))
+    }
+
+    /// Maximize or restore the window
+    #[staticmethod]
+    fn maximized(maximized: bool) -> Self {
+        Self(egui::ViewportCommand::Maximized(maximized))
+    }
+
+    /// Minimize or restore the window
+    #[staticmethod]
+    fn minimized(minimized: bool) -> Self {
+        Self(egui::ViewportCommand::Minimized(minimized))
+    }
+
+    /// Set whether the window is resizable
+    #[staticmethod]
+    fn resizable(resizable: bool) -> Self {
+        Self(egui::ViewportCommand::Resizable(resizable))
+    }
+
+    /// Close the window
+    #[staticmethod]
+    fn close() -> Self {
+        Self(egui::ViewportCommand::Close)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ViewportCommand({:?})", self.0)
+    }
+}
+
+/// Str stores string value that can be referenced
+///
+/// Usage::
+///
+///     data = Str("")
+///     
+///     def update_func():
+///         heading(f"Value of the data is {data.value}")
+///         if button_clicked("Add :)"):
+///             data.value += ":) "
+#[pyclass]
+struct Str {
+    #[pyo3(get, set)]
+    value: String
+}
+
+#[pymethods]
+impl Str {
+    #[new]
+    fn new(value: String) -> Self {
+        Str { value }
+    }
+}
+
+
+/// Bool stores a boolean value that can be referenced
+///
+/// Usage::
+///
+///     data = Bool(False)
+///     
+///     def update_func():
+///         heading(f"Value of the data is {data.value}")
+///         # button will be shown only if the checkbox is checked 
+///         if data.value and button_clicked("set to False"):
+///             # hiding the button
+///             data.value = False
+///         checkbox(data, "Check me")
+#[pyclass]
+struct Bool {
+    #[pyo3(get, set)]
+    value: bool
+}
+
+#[pymethods]
+impl Bool {
+    #[new]
+    fn new(value: bool) -> Self {
+        Bool { value }
+    }
+}
+
+/// Int stores integer value that can be referenced
+///
+/// Usage::
+///
+///     data = Int(69)
+///     
+///     def update_func():
+///         heading(f"Value of the data is {data.value}")
+///         if button_clicked("Increment"):
+///             data.value += 1
+#[pyclass]
+struct Int {
+    #[pyo3(get, set)]
+    value: i32
+}
+
+#[pymethods]
+impl Int {
+    #[new]
+    fn new(value: i32) -> Self {
+        Int { value }
+    }
+}
+
+/// Float stores float value that can be referenced
+///
+/// Usage::
+///
+///     data = Float(69.0)
+///     
+///     def update_func():
+///         heading(f"Value of the data is {data.value}")
+///         if button_clicked("Increment"):
+///             # what can go wrong?
+///             data.value += 0.1
+#[pyclass]
+struct Float {
+    #[pyo3(get, set)]
+    value: f32
+}
+
+#[pymethods]
+impl Float {
+    #[new]
+    fn new(value: f32) -> Self {
+        Float { value }
+    }
+}
+
+/// Channel passes messages from background threads to the UI. Sending a message wakes the app up.
+///
+/// Usage::
+///
+///     results = Channel()
+///
+///     def worker(urls):
+///         for url in urls:
+///             results.send(requests.get(url).status_code)
+///
+///     log = []
+///
+///     def update_func(ctx):
+///         log.extend(results.drain())
+///         for code in log:
+///             label(str(code))
+#[pyclass(frozen)]
+struct Channel(Mutex<Vec<Py<PyAny>>>);
+
+#[pymethods]
+impl Channel {
+    #[new]
+    fn new() -> Self {
+        Channel(Mutex::new(Vec::new()))
+    }
+
+    /// Send a message. Can be called from any thread
+    fn send(&self, message: Py<PyAny>) {
+        self.0.lock().unwrap().push(message);
+        request_app_repaint();
+    }
+
+    /// Take all messages sent so far, oldest first
+    fn drain(&self) -> Vec<Py<PyAny>> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+}
+
+/// SharedStr stores a string that background threads can write while the UI reads it.
+///
+/// Every read and write of value is atomic, but reading and then writing is not.
+///
+/// Usage::
+///
+///     status = SharedStr("idle")
+///
+///     def worker():
+///         status.value = "downloading"
+///         download()
+///         status.value = "done"
+///
+///     def update_func(ctx):
+///         label(status.value)
+#[pyclass(frozen)]
+struct SharedStr(Mutex<String>);
+
+#[pymethods]
+impl SharedStr {
+    #[new]
+    fn new(value: String) -> Self {
+        SharedStr(Mutex::new(value))
+    }
+
+    #[getter]
+    fn value(&self) -> String {
+        self.0.lock().unwrap().clone()
+    }
+
+    #[setter]
+    fn set_value(&self, value: String) {
+        *self.0.lock().unwrap() = value;
+    }
+}
+
+/// SharedBool stores a boolean that background threads can write while the UI reads it.
+///
+/// Usage::
+///
+///     cancelled = SharedBool(False)
+///
+///     def worker():
+///         while not cancelled.value:
+///             step()
+#[pyclass(frozen)]
+struct SharedBool(AtomicBool);
+
+#[pymethods]
+impl SharedBool {
+    #[new]
+    fn new(value: bool) -> Self {
+        SharedBool(AtomicBool::new(value))
+    }
+
+    #[getter]
+    fn value(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    #[setter]
+    fn set_value(&self, value: bool) {
+        self.0.store(value, Ordering::SeqCst);
+    }
+}
+
+/// SharedInt stores an integer that background threads can write while the UI reads it.
+///
+/// Use add instead of value += n when several threads change it.
+///
+/// Usage::
+///
+///     done = SharedInt(0)
+///
+///     def worker(files):
+///         for f in files:
+///             process(f)
+///             done.add(1)
+///
+///     def update_func(ctx):
+///         label(f"{done.value} files processed")
+#[pyclass(frozen)]
+struct SharedInt(AtomicI32);
+
+#[pymethods]
+impl SharedInt {
+    #[new]
+    fn new(value: i32) -> Self {
+        SharedInt(AtomicI32::new(value))
+    }
+
+    #[getter]
+    fn value(&self) -> i32 {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    #[setter]
+    fn set_value(&self, value: i32) {
+        self.0.store(value, Ordering::SeqCst);
+    }
+
+    /// Atomically add delta and return the new value
+    fn add(&self, delta: i32) -> i32 {
+        self.0.fetch_add(delta, Ordering::SeqCst).wrapping_add(delta)
+    }
+}
+
+/// SharedFloat stores a float that background threads can write while the UI reads it.
+///
+/// Usage::
+///
+///     progress = SharedFloat(0.0)
+///
+///     def worker():
+///         for i in range(100):
+///             step()
+///             progress.value = (i + 1) / 100
+///
+///     def update_func(ctx):
+///         label(f"{progress.value:.0%}")
+#[pyclass(frozen)]
+struct SharedFloat(Mutex<f32>);
+
+#[pymethods]
+impl SharedFloat {
+    #[new]
+    fn new(value: f32) -> Self {
+        SharedFloat(Mutex::new(value))
+    }
+
+    #[getter]
+    fn value(&self) -> f32 {
+        *self.0.lock().unwrap()
+    }
+
+    #[setter]
+    fn set_value(&self, value: f32) {
+        *self.0.lock().unwrap() = value;
+    }
+
+    /// Atomically add delta and return the new value
+    fn add(&self, delta: f32) -> f32 {
+        let mut value = self.0.lock().unwrap();
+        *value += delta;
+        *value
+    }
+}
+
+
+/// Rgb color picker
+///
+/// Usage::
+///
+///     color_rgb = RGB(69, 69, 69)
+///     color_edit_button_rgb(color_rgb)
+#[pyclass]
+#[allow(clippy::upper_case_acronyms)]
+struct RGB {
+  #[pyo3(get, set)]
+  r: f32,
+  #[pyo3(get, set)]
+  g: f32,
+  #[pyo3(get, set)]
+  b: f32,
+}
+
+#[pymethods]
+impl RGB {
+    #[new]
+    fn new(r: f32, g: f32, b: f32) -> Self {
+        RGB { r, g, b }
+    }
+}
+
+/// Rgba color picker. Components are in the 0-1 range, alpha is not premultiplied
+///
+/// Usage::
+///
+///     color_rgba = RGBA(1.0, 0.5, 0.0, 0.5)
+///     color_edit_button_rgba(color_rgba)
+#[pyclass]
+#[allow(clippy::upper_case_acronyms)]
+struct RGBA {
+  #[pyo3(get, set)]
+  r: f32,
+  #[pyo3(get, set)]
+  g: f32,
+  #[pyo3(get, set)]
+  b: f32,
+  #[pyo3(get, set)]
+  a: f32,
+}
+
+#[pymethods]
+impl RGBA {
+    #[new]
+    fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        RGBA { r, g, b, a }
+    }
+}
+
+/// Color with 8-bit channels in sRGB space. It can be used anywhere a color is needed.
+///
+/// Named colors are available as class attributes: Color.RED, Color.GREEN, Color.GRAY etc.
+///
+/// Usage::
+///
+///     label("error", color=Color.RED)
+///     label("custom", color=Color.from_hex("#ff8800"))
+///     label("translucent", color=Color(255, 0, 0, 128))
+#[pyclass(eq)]
+#[derive(Clone, PartialEq)]
+struct Color(egui::Color32);
+
+#[pymethods]
+impl Color {
+    #[new]
+    #[pyo3(signature = (r, g, b, a = 255))]
+    fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color(egui::Color32::from_rgba_unmultiplied(r, g, b, a))
+    }
+
+    /// Parse a color from "#rrggbb" or "#rrggbbaa"
+    #[staticmethod]
+    fn from_hex(hex: &str) -> PyResult<Self> {
+        egui::Color32::from_hex(hex)
+          .map(Color)
+          .map_err(|e| PyValueError::new_err(format!("Invalid hex color '{}': {:?}", hex, e)))
+    }
+
+    /// Format the color as "#rrggbbaa"
+    fn to_hex(&self) -> String {
+        self.0.to_hex()
+    }
+
+    #[getter]
+    fn r(&self) -> u8 {
+        self.0.to_srgba_unmultiplied()[0]
+    }
+
+    #[getter]
+    fn g(&self) -> u8 {
+        self.0.to_srgba_unmultiplied()[1]
+    }
+
+    #[getter]
+    fn b(&self) -> u8 {
+        self.0.to_srgba_unmultiplied()[2]
+    }
+
+    #[getter]
+    fn a(&self) -> u8 {
+        self.0.a()
+    }
+
+    fn __repr__(&self) -> String {
+        let [r, g, b, a] = self.0.to_srgba_unmultiplied();
+        format!("Color({}, {}, {}, {})", r, g, b, a)
+    }
+}
+
+/// Colors, shapes and spacing of the UI. Get the current one with ctx.style(), change it and apply it with ctx.set_style.
+///
+/// Colors can be anything accepted by color arguments: Color, "#rrggbb", RGB or RGBA.
+///
+/// Usage::
+///
+///     def update_func(ctx):
+///       style = ctx.style()
+///       style.window_fill = "#1e1e2e"
+///       style.selection_color = Color(255, 120, 0)
+///       style.set_widget_visuals("hovered", bg_fill="#3a3a5a", corner_radius=6)
+///       style.item_spacing = (12, 8)
+///       style.interact_size = (48, 48)
+///       ctx.set_style(style)
+#[pyclass]
+#[derive(Clone)]
+struct Style(egui::Style);
+
+#[pymethods]
+impl Style {
+    /// The default style of the theme: "dark" or "light"
+    #[new]
+    #[pyo3(signature = (theme = "dark"))]
+    fn new(theme: &str) -> PyResult<Self> {
+        Ok(Style(egui::Style {
+          visuals: match theme {
+            "dark" => egui::Visuals::dark(),
+            "light" => egui::Visuals::light(),
+            other => return Err(PyValueError::new_err(format!("Unknown theme '{}', expected 'dark' or 'light'", other)))
+          },
+          ..Default::default()
+        }))
+    }
+
+    /// True if the colors are meant for a dark background
+    #[getter]
+    fn dark_mode(&self) -> bool {
+        self.0.visuals.dark_mode
+    }
+
+    #[setter]
+    fn set_dark_mode(&mut self, dark_mode: bool) {
+        self.0.visuals.dark_mode = dark_mode;
+    }
+
+    /// Color of all text, or None to use the colors of the widgets
+    #[getter]
+    fn override_text_color(&self) -> Option<Color> {
+        self.0.visuals.override_text_color.map(Color)
+    }
+
+    #[setter]
+    fn set_override_text_color(&mut self, color: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        self.0.visuals.override_text_color = match color {
+          Some(color) if !color.is_none() => Some(extract_color(color)?),
+          _ => None
+        };
+        Ok(())
+    }
+
+    /// Background of windows
+    #[getter]
+    fn window_fill(&self) -> Color {
+        Color(self.0.visuals.window_fill)
+    }
+
+    #[setter]
+    fn set_window_fill(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.window_fill = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Background of panels, including the main area
+    #[getter]
+    fn panel_fill(&self) -> Color {
+        Color(self.0.visuals.panel_fill)
+    }
+
+    #[setter]
+    fn set_panel_fill(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.panel_fill = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Background of text fields, scroll bars and plots
+    #[getter]
+    fn extreme_bg_color(&self) -> Color {
+        Color(self.0.visuals.extreme_bg_color)
+    }
+
+    #[setter]
+    fn set_extreme_bg_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.extreme_bg_color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Background of striped table rows
+    #[getter]
+    fn faint_bg_color(&self) -> Color {
+        Color(self.0.visuals.faint_bg_color)
+    }
+
+    #[setter]
+    fn set_faint_bg_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.faint_bg_color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Background of code
+    #[getter]
+    fn code_bg_color(&self) -> Color {
+        Color(self.0.visuals.code_bg_color)
+    }
+
+    #[setter]
+    fn set_code_bg_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.code_bg_color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Color of hyperlinks
+    #[getter]
+    fn hyperlink_color(&self) -> Color {
+        Color(self.0.visuals.hyperlink_color)
+    }
+
+    #[setter]
+    fn set_hyperlink_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.hyperlink_color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Color of warnings
+    #[getter]
+    fn warn_fg_color(&self) -> Color {
+        Color(self.0.visuals.warn_fg_color)
+    }
+
+    #[setter]
+    fn set_warn_fg_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.warn_fg_color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Color of errors
+    #[getter]
+    fn error_fg_color(&self) -> Color {
+        Color(self.0.visuals.error_fg_color)
+    }
+
+    #[setter]
+    fn set_error_fg_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.error_fg_color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Background of selected text and selected widgets
+    #[getter]
+    fn selection_color(&self) -> Color {
+        Color(self.0.visuals.selection.bg_fill)
+    }
+
+    #[setter]
+    fn set_selection_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.selection.bg_fill = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Color of the text and outline of selected widgets
+    #[getter]
+    fn selection_stroke_color(&self) -> Color {
+        Color(self.0.visuals.selection.stroke.color)
+    }
+
+    #[setter]
+    fn set_selection_stroke_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.selection.stroke.color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Color of the window outline
+    #[getter]
+    fn window_stroke_color(&self) -> Color {
+        Color(self.0.visuals.window_stroke.color)
+    }
+
+    #[setter]
+    fn set_window_stroke_color(&mut self, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.visuals.window_stroke.color = extract_color(color)?;
+        Ok(())
+    }
+
+    /// Width of the window outline
+    #[getter]
+    fn window_stroke_width(&self) -> f32 {
+        self.0.visuals.window_stroke.width
+    }
+
+    #[setter]
+    fn set_window_stroke_width(&mut self, width: f32) {
+        self.0.visuals.window_stroke.width = width;
+    }
+
+    /// Corner radius of windows
+    #[getter]
+    fn window_corner_radius(&self) -> u8 {
+        self.0.visuals.window_corner_radius.nw
+    }
+
+    #[setter]
+    fn set_window_corner_radius(&mut self, radius: u8) {
+        self.0.visuals.window_corner_radius = egui::CornerRadius::same(radius);
+    }
+
+    /// Corner radius of menus and popups
+    #[getter]
+    fn menu_corner_radius(&self) -> u8 {
+        self.0.visuals.menu_corner_radius.nw
+    }
+
+    #[setter]
+    fn set_menu_corner_radius(&mut self, radius: u8) {
+        self.0.visuals.menu_corner_radius = egui::CornerRadius::same(radius);
+    }
+
+    /// Horizontal and vertical space between widgets
+    #[getter]
+    fn item_spacing(&self) -> (f32, f32) {
+        let s = self.0.spacing.item_spacing;
+        (s.x, s.y)
+    }
+
+    #[setter]
+    fn set_item_spacing(&mut self, spacing: (f32, f32)) {
+        self.0.spacing.item_spacing = spacing.into();
+    }
+
+    /// Horizontal and vertical space between the text of a button and its outline
+    #[getter]
+    fn button_padding(&self) -> (f32, f32) {
+        let p = self.0.spacing.button_padding;
+        (p.x, p.y)
+    }
+
+    #[setter]
+    fn set_button_padding(&mut self, padding: (f32, f32)) {
+        self.0.spacing.button_padding = padding.into();
+    }
+
+    /// Indentation of e.g. the content of collapsing headers and tree nodes
+    #[getter]
+    fn indent(&self) -> f32 {
+        self.0.spacing.indent
+    }
+
+    #[setter]
+    fn set_indent(&mut self, indent: f32) {
+        self.0.spacing.indent = indent;
+    }
+
+    /// Minimal width and height of interactive widgets. Make it bigger for touch screens
+    #[getter]
+    fn interact_size(&self) -> (f32, f32) {
+        let s = self.0.spacing.interact_size;
+        (s.x, s.y)
+    }
+
+    #[setter]
+    fn set_interact_size(&mut self, size: (f32, f32)) {
+        self.0.spacing.interact_size = size.into();
+    }
+
+    /// Default width of sliders
+    #[getter]
+    fn slider_width(&self) -> f32 {
+        self.0.spacing.slider_width
+    }
+
+    #[setter]
+    fn set_slider_width(&mut self, width: f32) {
+        self.0.spacing.slider_width = width;
+    }
+
+    /// Default width of combo boxes
+    #[getter]
+    fn combo_width(&self) -> f32 {
+        self.0.spacing.combo_width
+    }
+
+    #[setter]
+    fn set_combo_width(&mut self, width: f32) {
+        self.0.spacing.combo_width = width;
+    }
+
+    /// Default width of text fields
+    #[getter]
+    fn text_edit_width(&self) -> f32 {
+        self.0.spacing.text_edit_width
+    }
+
+    #[setter]
+    fn set_text_edit_width(&mut self, width: f32) {
+        self.0.spacing.text_edit_width = width;
+    }
+
+    /// Size of checkboxes and radio buttons
+    #[getter]
+    fn icon_width(&self) -> f32 {
+        self.0.spacing.icon_width
+    }
+
+    #[setter]
+    fn set_icon_width(&mut self, width: f32) {
+        self.0.spacing.icon_width = width;
+    }
+
+    /// Width of scroll bars
+    #[getter]
+    fn scroll_bar_width(&self) -> f32 {
+        self.0.spacing.scroll.bar_width
+    }
+
+    #[setter]
+    fn set_scroll_bar_width(&mut self, width: f32) {
+        self.0.spacing.scroll.bar_width = width;
+    }
+
+    /// Change how widgets look in a state. Only the given options are changed.
+    ///
+    /// States are "noninteractive" (labels, separators), "inactive" (e.g. a button that isn't hovered),
+    /// "hovered", "active" (pressed or dragged) and "open" (e.g. an open combo box).
+    ///
+    /// Args:
+    ///     state (str): state of the widgets
+    ///
+    ///     bg_fill (Color): background of checkboxes, radio buttons and slider handles
+    ///
+    ///     weak_bg_fill (Color): background of buttons and combo boxes
+    ///
+    ///     fg_color (Color): color of text and icons
+    ///
+    ///     fg_stroke_width (float): width of lines drawn on widgets, e.g. the checkmark
+    ///
+    ///     stroke_color (Color): color of the outline
+    ///
+    ///     stroke_width (float): width of the outline
+    ///
+    ///     corner_radius (int): corner radius of the widgets
+    ///
+    ///     expansion (float): how much the background grows, e.g. when hovered
+    #[pyo3(signature = (state, **kwargs))]
+    fn set_widget_visuals(&mut self, state: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        let widgets = &mut self.0.visuals.widgets;
+        let visuals = match state {
+          "noninteractive" => &mut widgets.noninteractive,
+          "inactive" => &mut widgets.inactive,
+          "hovered" => &mut widgets.hovered,
+          "active" => &mut widgets.active,
+          "open" => &mut widgets.open,
+          other => return Err(PyValueError::new_err(format!("Unknown widget state '{}'", other)))
+        };
+
+        if let Some(kwargs) = kwargs {
+
+          if let Some(color) = kwargs.get_item("bg_fill")? {
+            visuals.bg_fill = extract_color(&color)?;
+          }
+
+          if let Some(color) = kwargs.get_item("weak_bg_fill")? {
+            visuals.weak_bg_fill = extract_color(&color)?;
+          }
+
+          if let Some(color) = kwargs.get_item("fg_color")? {
+            visuals.fg_stroke.color = extract_color(&color)?;
+          }
+
+          if let Some(width) = kwargs.get_item("fg_stroke_width")? {
+            visuals.fg_stroke.width = width.extract()?;
+          }
+
+          if let Some(color) = kwargs.get_item("stroke_color")? {
+            visuals.bg_stroke.color = extract_color(&color)?;
+          }
+
+          if let Some(width) = kwargs.get_item("stroke_width")? {
+            visuals.bg_stroke.width = width.extract()?;
+          }
+
+          if let Some(radius) = kwargs.get_item("corner_radius")? {
+            visuals.corner_radius = egui::CornerRadius::same(radius.extract()?);
+          }
+
+          if let Some(expansion) = kwargs.get_item("expansion")? {
+            visuals.expansion = expansion.extract()?;
+          }
+        }
+
+        Ok(())
+    }
+}
+
+/// Hsva color picker. Hue, saturation, value and alpha are in the 0-1 range
+///
+/// Usage::
+///
+///     color_hsva = HSVA(0.5, 1.0, 1.0, 1.0)
+///     color_edit_button_hsva(color_hsva)
+///     rgb = color_hsva.to_rgb()
+#[pyclass]
+#[allow(clippy::upper_case_acronyms)]
+struct HSVA {
+  #[pyo3(get, set)]
+  h: f32,
+  #[pyo3(get, set)]
+  s: f32,
+  #[pyo3(get, set)]
+  v: f32,
+  #[pyo3(get, set)]
+  a: f32,
+}
+
+impl HSVA {
+    fn to_hsva(&self) -> egui::epaint::Hsva {
+        egui::epaint::Hsva::new(self.h, self.s, self.v, self.a)
+    }
+}
+
+impl From<egui::epaint::Hsva> for HSVA {
+    fn from(hsva: egui::epaint::Hsva) -> Self {
+        HSVA { h: hsva.h, s: hsva.s, v: hsva.v, a: hsva.a }
+    }
+}
+
+#[pymethods]
+impl HSVA {
+    #[new]
+    #[pyo3(signature = (h, s, v, a = 1.0))]
+    fn new(h: f32, s: f32, v: f32, a: f32) -> Self {
+        HSVA { h, s, v, a }
+    }
+
+    /// Convert RGB to HSVA
+    #[staticmethod]
+    fn from_rgb(rgb: &RGB) -> Self {
+        egui::epaint::Hsva::from_rgb([rgb.r, rgb.g, rgb.b]).into()
+    }
+
+    /// Convert RGBA to HSVA
+    #[staticmethod]
+    fn from_rgba(rgba: &RGBA) -> Self {
+        egui::epaint::Hsva::from_rgba_unmultiplied(rgba.r, rgba.g, rgba.b, rgba.a).into()
+    }
+
+    /// Convert to RGB, alpha is dropped
+    fn to_rgb(&self) -> RGB {
+        let [r, g, b] = self.to_hsva().to_rgb();
+        RGB { r, g, b }
+    }
+
+    /// Convert to RGBA
+    fn to_rgba(&self) -> RGBA {
+        let [r, g, b, a] = self.to_hsva().to_rgba_unmultiplied();
+        RGBA { r, g, b, a }
+    }
+}
+
+/// Date picker
+///
+/// Usage::
+///
+///     date = Date(datetime.datetime.now())
+///     date_picker_button(date)
+#[pyclass]
+struct Date {
+    #[pyo3(get, set)]
+    value: NaiveDate
+}
+
+#[pymethods]
+impl Date {
+    #[new]
+    fn new(value: NaiveDate) -> Self {
+        Date { value }
+    }
+}
+
+/// Time picker
+///
+/// Usage::
+///
+///     time = Time(datetime.time(12, 30))
+///     time_picker(time)
+#[pyclass]
+struct Time {
+    #[pyo3(get, set)]
+    value: NaiveTime
+}
+
+#[pymethods]
+impl Time {
+    #[new]
+    fn new(value: NaiveTime) -> Self {
+        Time { value }
+    }
+}
+
+/// Date and time picker
+///
+/// Usage::
+///
+///     moment = DateTime(datetime.datetime.now())
+///     datetime_picker(moment)
+#[pyclass]
+struct DateTime {
+    #[pyo3(get, set)]
+    value: NaiveDateTime
+}
+
+#[pymethods]
+impl DateTime {
+    #[new]
+    fn new(value: NaiveDateTime) -> Self {
+        DateTime { value }
+    }
+}
+
+/// Rectangle in points, defined by its min (top left) and max (bottom right) corners
+///
+/// Usage::
+///
+///     r = label("hi").rect
+///     heading(f"label is {r.width}x{r.height}")
+#[pyclass]
+#[derive(Clone, Copy)]
+struct Rect(egui::Rect);
+
+#[pymethods]
+impl Rect {
+    #[new]
+    fn new(min: (f32, f32), max: (f32, f32)) -> Self {
+        Rect(egui::Rect::from_min_max(min.into(), max.into()))
+    }
+
+    /// Top left corner
+    #[getter]
+    fn min(&self) -> (f32, f32) {
+        (self.0.min.x, self.0.min.y)
+    }
+
+    /// Bottom right corner
+    #[getter]
+    fn max(&self) -> (f32, f32) {
+        (self.0.max.x, self.0.max.y)
+    }
+
+    #[getter]
+    fn center(&self) -> (f32, f32) {
+        (self.0.center().x, self.0.center().y)
+    }
+
+    #[getter]
+    fn width(&self) -> f32 {
+        self.0.width()
+    }
+
+    #[getter]
+    fn height(&self) -> f32 {
+        self.0.height()
+    }
+
+    /// True if the point is inside the rectangle
+    fn contains(&self, pos: (f32, f32)) -> bool {
+        self.0.contains(pos.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Rect({:?}, {:?})", self.min(), self.max())
+    }
+}
+
+/// Draws shapes and text at screen coordinates. Get one with painter().
+///
+/// Colors can be Color, a hex string or a tuple. When a color is None, the text color of the current theme is used.
+///
+/// Usage::
+///
+///     p = painter()
+///     r = label("Temperature").rect
+///     p.line_segment(r.min, (r.max[0], r.min[1]), color="#ff0000")
+///     p.text(r.center, "center_center", "42", font_size=20.0)
+#[pyclass]
+#[derive(Clone)]
+struct Painter(egui::Painter);
+
+#[pymethods]
+impl Painter {
+
+    /// Nothing outside this rectangle is painted
+    #[getter]
+    fn clip_rect(&self) -> Rect {
+        Rect(self.0.clip_rect())
+    }
+
+    /// Draw a line between two points
+    #[pyo3(signature = (start, end, width = 1.0, color = None))]
+    fn line_segment(&self, start: (f32, f32), end: (f32, f32), width: f32, color: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        self.0.line_segment([start.into(), end.into()], egui::Stroke::new(width, self.color_or_default(color)?));
+        Ok(())
+    }
+
+    /// Fill a rectangle
+    #[pyo3(signature = (rect, color, corner_radius = 0.0))]
+    fn rect_filled(&self, rect: &Rect, color: &Bound<'_, PyAny>, corner_radius: f32) -> PyResult<()> {
+        self.0.rect_filled(rect.0, corner_radius, extract_color(color)?);
+        Ok(())
+    }
+
+    /// Draw the outline of a rectangle
+    #[pyo3(signature = (rect, width = 1.0, color = None, corner_radius = 0.0))]
+    fn rect_stroke(&self, rect: &Rect, width: f32, color: Option<&Bound<'_, PyAny>>, corner_radius: f32) -> PyResult<()> {
+        let stroke = egui::Stroke::new(width, self.color_or_default(color)?);
+        self.0.rect_stroke(rect.0, corner_radius, stroke, egui::StrokeKind::Inside);
+        Ok(())
+    }
+
+    /// Fill a circle
+    fn circle_filled(&self, center: (f32, f32), radius: f32, color: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.circle_filled(center.into(), radius, extract_color(color)?);
+        Ok(())
+    }
+
+    /// Draw the outline of a circle
+    #[pyo3(signature = (center, radius, width = 1.0, color = None))]
+    fn circle_stroke(&self, center: (f32, f32), radius: f32, width: f32, color: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        self.0.circle_stroke(center.into(), radius, egui::Stroke::new(width, self.color_or_default(color)?));
+        Ok(())
+    }
+
+    /// Draw a line through the points
+    ///
+    /// Example::
+    ///
+    ///     p.line([(0, 0), (50, 20), (100, 0)], width=2.0, color="#00ff00")
+    #[pyo3(signature = (points, width = 1.0, color = None))]
+    fn line(&self, points: &Bound<'_, PyAny>, width: f32, color: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        let stroke = egui::Stroke::new(width, self.color_or_default(color)?);
+        self.0.add(egui::Shape::line(extract_path_points(points)?, stroke));
+        Ok(())
+    }
+
+    /// Draw a closed polygon. Only convex polygons can be filled.
+    ///
+    /// Args:
+    ///     points (list[tuple[float, float]] | numpy.ndarray): corners of the polygon
+    ///
+    ///     fill (Color | str | tuple[int, int, int] | None): fill color. By default the polygon is not filled
+    ///
+    ///     width (float): width of the outline, 0 to draw no outline. Default is 1.0
+    ///
+    ///     color (Color | str | tuple[int, int, int] | None): color of the outline
+    #[pyo3(signature = (points, fill = None, width = 1.0, color = None))]
+    fn polygon(
+        &self,
+        points: &Bound<'_, PyAny>,
+        fill: Option<&Bound<'_, PyAny>>,
+        width: f32,
+        color: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
+        let points = extract_path_points(points)?;
+        let stroke = egui::Stroke::new(width, self.color_or_default(color)?);
+
+        self.0.add(egui::Shape::convex_polygon(points, extract_fill(fill)?, stroke));
+        Ok(())
+    }
+
+    /// Draw a quadratic bezier curve from the first point to the last one, bent towards the middle one.
+    /// Accepts the same arguments as cubic_bezier.
+    #[pyo3(signature = (points, width = 1.0, color = None, fill = None, closed = false))]
+    fn quadratic_bezier(
+        &self,
+        points: &Bound<'_, PyAny>,
+        width: f32,
+        color: Option<&Bound<'_, PyAny>>,
+        fill: Option<&Bound<'_, PyAny>>,
+        closed: bool,
+    ) -> PyResult<()> {
+        let points: [egui::Pos2; 3] = extract_path_points(points)?
+          .try_into()
+          .map_err(|_| PyValueError::new_err("quadratic_bezier needs exactly 3 points"))?;
+        let stroke = egui::Stroke::new(width, self.color_or_default(color)?);
+
+        let shape = egui::epaint::QuadraticBezierShape::from_points_stroke(points, closed, extract_fill(fill)?, stroke);
+        self.0.add(shape);
+        Ok(())
+    }
+
+    /// Draw a cubic bezier curve from the first point to the last one, bent towards the two middle ones.
+    ///
+    /// Args:
+    ///     points (list[tuple[float, float]] | numpy.ndarray): start, two control points and end
+    ///
+    ///     width (float): width of the curve. Default is 1.0
+    ///
+    ///     color (Color | str | tuple[int, int, int] | None): color of the curve
+    ///
+    ///     fill (Color | str | tuple[int, int, int] | None): fill the area between the curve and the line connecting its ends
+    ///
+    ///     closed (bool): also draw the line connecting the ends. Default is False
+    ///
+    /// Example::
+    ///
+    ///     # an edge of a node graph
+    ///     (x1, y1), (x2, y2) = out_pin, in_pin
+    ///     p.cubic_bezier([(x1, y1), (x1 + 50, y1), (x2 - 50, y2), (x2, y2)], width=2.0)
+    #[pyo3(signature = (points, width = 1.0, color = None, fill = None, closed = false))]
+    fn cubic_bezier(
+        &self,
+        points: &Bound<'_, PyAny>,
+        width: f32,
+        color: Option<&Bound<'_, PyAny>>,
+        fill: Option<&Bound<'_, PyAny>>,
+        closed: bool,
+    ) -> PyResult<()> {
+        let points: [egui::Pos2; 4] = extract_path_points(points)?
+          .try_into()
+          .map_err(|_| PyValueError::new_err("cubic_bezier needs exactly 4 points"))?;
+        let stroke = egui::Stroke::new(width, self.color_or_default(color)?);
+
+        let shape = egui::epaint::CubicBezierShape::from_points_stroke(points, closed, extract_fill(fill)?, stroke);
+        self.0.add(shape);
+        Ok(())
+    }
+
+    /// Draw text and return the Rect it occupies.
+    ///
+    /// Args:
+    ///     pos (tuple[float, float]): where to put the anchor of the text
+    ///
+    ///     anchor (str): which point of the text is placed at pos, e.g. "left_top", "center_center" or "right_bottom"
+    ///
+    ///     text (str): text to draw
+    ///
+    ///     font_size (float): size of the font. Default is 14.0
+    ///
+    ///     color (Color | str | tuple[int, int, int] | None): color of the text
+    ///
+    ///     monospace (bool): use the monospace font. Default is False
+    ///
+    /// Example::
+    ///
+    ///     for i, x in enumerate(ticks):
+    ///         p.line_segment((x, y), (x, y + 4))
+    ///         p.text((x, y + 6), "center_top", str(i), font_size=10.0)
+    #[pyo3(signature = (pos, anchor, text, font_size = 14.0, color = None, monospace = false))]
+    fn text(
+        &self,
+        pos: (f32, f32),
+        anchor: &str,
+        text: &str,
+        font_size: f32,
+        color: Option<&Bound<'_, PyAny>>,
+        monospace: bool,
+    ) -> PyResult<Rect> {
+        let rect = self.0.text(pos.into(), extract_align2(anchor)?, text, font_id(font_size, monospace), self.color_or_default(color)?);
+        Ok(Rect(rect))
+    }
+
+    /// Measure text without drawing it. Returns (width, height).
+    ///
+    /// Args:
+    ///     text (str): text to measure
+    ///
+    ///     font_size (float): size of the font. Default is 14.0
+    ///
+    ///     monospace (bool): use the monospace font. Default is False
+    ///
+    ///     wrap_width (float | None): wrap the text at this width. By default the text is not wrapped
+    ///
+    /// Example::
+    ///
+    ///     w, h = p.text_size("Temperature", font_size=12.0)
+    ///     p.rect_filled(Rect((x, y), (x + w, y + h)), "#202020")
+    #[pyo3(signature = (text, font_size = 14.0, monospace = false, wrap_width = None))]
+    fn text_size(&self, text: &str, font_size: f32, monospace: bool, wrap_width: Option<f32>) -> (f32, f32) {
+        let font = font_id(font_size, monospace);
+        let color = egui::Color32::PLACEHOLDER;
+
+        let galley = match wrap_width {
+          Some(width) => self.0.layout(text.to_owned(), font, color, width),
+          None => self.0.layout_no_wrap(text.to_owned(), font, color)
+        };
+
+        (galley.size().x, galley.size().y)
+    }
+}
+
+impl Painter {
+    fn color_or_default(&self, color: Option<&Bound<'_, PyAny>>) -> PyResult<egui::Color32> {
+        match color {
+          Some(color) if !color.is_none() => extract_color(color),
+          _ => Ok(self.0.ctx().style().visuals.text_color())
+        }
+    }
+}
+
+/// The result of adding a widget to a Ui.
+///
+/// It lets you know whether the widget is being hovered, clicked or dragged.
+///
+/// Usage::
+///
+///     r = button("click me")
+///     if r.clicked():
+///         print("clicked")
+///     if r.hovered():
+///         label("hovering")
+#[pyclass]
+#[derive(Clone)]
+struct Response(egui::Response);
+
+#[pymethods]
+impl Response {
+
+    /// Returns true if this widget was clicked this frame by the primary button.
+    fn clicked(&self) -> bool {
+        self.0.clicked()
+    }
+
+    /// Returns true if this widget was clicked this frame by the secondary mouse button (e.g. the right mouse button).
+    fn secondary_clicked(&self) -> bool {
+        self.0.secondary_clicked()
+    }
+
+    /// Returns true if this widget was double-clicked this frame by the primary button.
+    fn double_clicked(&self) -> bool {
+        self.0.double_clicked()
+    }
+
+    /// Returns true if this widget was triple-clicked this frame by the primary button.
+    fn triple_clicked(&self) -> bool {
+        self.0.triple_clicked()
+    }
+
+    /// The pointer is hovering above this widget or the widget was clicked by keyboard interaction.
+    fn hovered(&self) -> bool {
+        self.0.hovered()
+    }
+
+    /// The underlying data changed. E.g. the slider was dragged or the text was edited.
+    fn changed(&self) -> bool {
+        self.0.changed()
+    }
+
+    /// The widget is being dragged.
+    fn dragged(&self) -> bool {
+        self.0.dragged()
+    }
+
+    /// The widget started being dragged this frame.
+    fn drag_started(&self) -> bool {
+        self.0.drag_started()
+    }
+
+    /// The widget was being dragged, but now it has been released.
+    fn drag_stopped(&self) -> bool {
+        self.0.drag_stopped()
+    }
+
+    /// If dragged, how many points were we dragged and in what direction?
+    fn drag_delta(&self) -> (f32, f32) {
+        let d = self.0.drag_delta();
+        (d.x, d.y)
+    }
+
+    /// Payload of a dnd_drag_source dragged over the widget, or None
+    fn dnd_hover_payload(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.0.dnd_hover_payload::<DragPayload>().map(|p| p.0.clone_ref(py))
+    }
+
+    /// Payload of a dnd_drag_source dropped onto the widget this frame, or None
+    ///
+    /// Example::
+    ///
+    ///     for i, item in enumerate(items):
+    ///         r = dnd_drag_source(f"item{i}", i, lambda: label(item))
+    ///         dropped = r.dnd_release_payload()
+    ///         if dropped is not None:
+    ///             items.insert(i, items.pop(dropped))
+    fn dnd_release_payload(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.0.dnd_release_payload::<DragPayload>().map(|p| p.0.clone_ref(py))
+    }
+
+    /// This widget has the keyboard focus (i.e. is receiving key presses).
+    fn has_focus(&self) -> bool {
+        self.0.has_focus()
+    }
+
+    /// The widget gained keyboard focus this frame.
+    fn gained_focus(&self) -> bool {
+        self.0.gained_focus()
+    }
+
+    /// The widget had keyboard focus and lost it this frame.
+    fn lost_focus(&self) -> bool {
+        self.0.lost_focus()
+    }
+
+    /// Enter was pressed in a singleline text field this frame. Use it to submit forms.
+    ///
+    /// Example::
+    ///
+    ///     if text_edit_singleline(query).submitted():
+    ///       search(query.value)
+    fn submitted(&self) -> bool {
+        self.0.lost_focus() && self.0.ctx.input(|i| i.key_pressed(egui::Key::Enter))
+    }
+
+    /// Was the widget enabled? If false, there was no interaction attempted and the widget should be drawn in a gray disabled look.
+    fn enabled(&self) -> bool {
+        self.0.enabled()
+    }
+
+    /// The area of the screen we are talking about.
+    #[getter]
+    fn rect(&self) -> Rect {
+        Rect(self.0.rect)
+    }
+
+    /// Show this text if the widget was hovered (i.e. a tooltip).
+    ///
+    /// Example::
+    ///
+    ///     button("save").on_hover_text("Save the document to disk")
+    fn on_hover_text(&self, text: &str) -> Response {
+        Response(self.0.clone().on_hover_text(text))
+    }
+
+    /// Like on_hover_text, but show the text next to cursor.
+    fn on_hover_text_at_pointer(&self, text: &str) -> Response {
+        Response(self.0.clone().on_hover_text_at_pointer(text))
+    }
+
+    /// Show this text when hovering if the widget is disabled.
+    fn on_disabled_hover_text(&self, text: &str) -> Response {
+        Response(self.0.clone().on_disabled_hover_text(text))
+    }
+
+    /// Show a tooltip built by update_fun if the widget was hovered.
+    ///
+    /// Example::
+    ///
+    ///     def tooltip():
+    ///       heading("Ferris")
+    ///       image("file://assets/ferris.png", max_width=64)
+    ///
+    ///     label("hover me").on_hover_ui(tooltip)
+    unsafe fn on_hover_ui(&self, update_fun: Bound<'_, PyAny>) -> PyResult<Response> {
+        let mut result = Ok(());
+        let r = self.0.clone().on_hover_ui(|ui| result = run_nested_update_func(ui, update_fun));
+
+        result.map(|_| Response(r))
+    }
+
+    /// Like on_hover_ui, but show the ui next to cursor.
+    unsafe fn on_hover_ui_at_pointer(&self, update_fun: Bound<'_, PyAny>) -> PyResult<Response> {
+        let mut result = Ok(());
+        let r = self.0.clone().on_hover_ui_at_pointer(|ui| result = run_nested_update_func(ui, update_fun));
+
+        result.map(|_| Response(r))
+    }
+
+    /// The selected range of a text field as (start, end) character indices. start == end when nothing is selected.
+    /// None if the text field has never been focused
+    fn text_cursor(&self) -> Option<(usize, usize)> {
+        let state = egui::widgets::text_edit::TextEditState::load(&self.0.ctx, self.0.id)?;
+        let [start, end] = state.cursor.char_range()?.sorted();
+
+        Some((start.index, end.index))
+    }
+
+    /// Focus the text field and select the characters from start to end. Only moves the cursor if end is omitted
+    ///
+    /// Example::
+    ///
+    ///     r = text_edit_multiline(text)
+    ///     if button_clicked("Go to start"):
+    ///       r.set_text_cursor(0)
+    #[pyo3(signature = (start, end = None))]
+    fn set_text_cursor(&self, start: usize, end: Option<usize>) {
+        self.set_char_range(start, end.unwrap_or(start));
+    }
+
+    /// Focus the text field and select all of its text
+    fn select_all(&self, text: &Str) {
+        self.set_char_range(0, text.value.chars().count());
+    }
+
+    /// Replace the selected text of the text field with insert and move the cursor after it.
+    /// If the text field has no cursor, the text is appended
+    ///
+    /// Example::
+    ///
+    ///     r = text_edit_multiline(text)
+    ///     if button_clicked("Insert date"):
+    ///       r.insert_text(text, str(datetime.date.today()))
+    fn insert_text(&self, text: &mut Str, insert: &str) {
+        let len = text.value.chars().count();
+        let (start, end) = self.text_cursor().unwrap_or((len, len));
+        let (start, end) = (start.min(len), end.min(len));
+
+        let byte = |index: usize| text.value.char_indices().nth(index).map_or(text.value.len(), |(i, _)| i);
+        let range = byte(start)..byte(end);
+        text.value.replace_range(range, insert);
+
+        let cursor = start + insert.chars().count();
+        self.set_char_range(cursor, cursor);
+    }
+}
+
+impl Response {
+    fn set_char_range(&self, start: usize, end: usize) {
+        let ctx = &self.0.ctx;
+        let id = self.0.id;
+        let mut state = egui::widgets::text_edit::TextEditState::load(ctx, id).unwrap_or_default();
+
+        state.cursor.set_char_range(Some(egui::text::CCursorRange::two(
+          egui::text::CCursor::new(start),
+          egui::text::CCursor::new(end)
+        )));
+        state.store(ctx, id);
+        ctx.memory_mut(|m| m.request_focus(id));
+    }
+}
+
+/// Image uploaded to the GPU. Create it with load_texture and show it with textured_image.
+/// Changing it with set or update doesn't require decoding the image again.
+///
+/// Usage::
+///
+///     texture = None
+///
+///     def update_func(ctx):
+///         global texture
+///         if texture is None:
+///             texture = load_texture("canvas", pixels)
+///         if pixels_changed():
+///             texture.set(pixels)
+///         textured_image(texture)
+#[pyclass]
+struct TextureHandle(egui::TextureHandle);
+
+#[pymethods]
+impl TextureHandle {
+
+    /// Replace the whole image
+    #[pyo3(signature = (image, filter = "linear"))]
+    fn set(&mut self, image: &Bound<'_, PyAny>, filter: &str) -> PyResult<()> {
+        self.0.set(extract_color_image(image)?, texture_options(filter)?);
+        Ok(())
+    }
+
+    /// Replace a part of the image starting at (x, y). The part must fit in the image
+    #[pyo3(signature = (x, y, image, filter = "linear"))]
+    fn update(&mut self, x: usize, y: usize, image: &Bound<'_, PyAny>, filter: &str) -> PyResult<()> {
+        let image = extract_color_image(image)?;
+        let [w, h] = self.0.size();
+
+        if x + image.width() > w || y + image.height() > h {
+          return Err(PyValueError::new_err(TEXTURE_UPDATE_ERR));
+        }
+
+        self.0.set_partial([x, y], image, texture_options(filter)?);
+        Ok(())
+    }
+
+    /// (width, height) of the image in pixels
+    #[getter]
+    fn size(&self) -> (usize, usize) {
+        let [w, h] = self.0.size();
+        (w, h)
+    }
+
+    /// Width of the image in pixels
+    #[getter]
+    fn width(&self) -> usize {
+        self.0.size()[0]
+    }
+
+    /// Height of the image in pixels
+    #[getter]
+    fn height(&self) -> usize {
+        self.0.size()[1]
+    }
+
+    /// width / height
+    #[getter]
+    fn aspect_ratio(&self) -> f32 {
+        self.0.aspect_ratio()
+    }
+
+    /// Name the texture was loaded with
+    #[getter]
+    fn name(&self) -> String {
+        self.0.name()
+    }
+}
+
+// python object attached to a drag
+struct DragPayload(Py<PyAny>);
+
+/// Container is returned by layout functions like horizontal, group or collapsing when they are called without update_fun.
+/// Use it in a with statement, widgets added inside of it go to the container.
+///
+/// Usage::
+///
+///     def update_func(ctx):
+///         with horizontal():
+///             label("Name:")
+///             text_edit_singleline(name)
+///
+///         with collapsing("Details") as open:
+///             if open:
+///                 label(expensive_details())
+#[pyclass(unsendable)]
+struct Container {
+    kind: ContainerKind,
+    // removes the child ui from the stack when the container is exited or dropped.
+    // Declared before entered, so that it is dropped before the child ui
+    guard: Option<UiGuard>,
+    entered: Option<EnteredContainer>,
+}
+
+enum ContainerKind {
+    // layout and the height of the child ui. None means all available height
+    Layout(egui::Layout, Option<f32>),
+    Indent,
+    Group,
+    Frame(egui::Frame),
+    Scope,
+    PushId(egui::Id),
+    Sized(egui::Vec2),
+    Collapsing(CollapsingOptions),
+}
+
+struct CollapsingOptions {
+    heading: String,
+    default_open: bool,
+    id_salt: Option<String>,
+    // reflects and controls whether the header is open
+    open: Option<Py<Bool>>,
+}
+
+// the child ui lives here between __enter__ and __exit__.
+// It is boxed, so that the pointer pushed to the ui stack stays valid
+enum EnteredContainer {
+    Child(Box<egui::Ui>),
+    Indent(Box<egui::Ui>),
+    Frame(Box<egui::frame::Prepared>),
+    // the body of a closed collapsing header is drawn invisibly and is not allocated in the parent
+    Hidden(Box<egui::Ui>),
+    // the space was allocated in the parent by __enter__
+    Allocated(Box<egui::Ui>),
+}
+
+impl EnteredContainer {
+    fn child_ui(&mut self) -> &mut egui::Ui {
+        match self {
+          EnteredContainer::Child(ui) | EnteredContainer::Indent(ui) | EnteredContainer::Hidden(ui) | EnteredContainer::Allocated(ui) => ui,
+          EnteredContainer::Frame(prepared) => &mut prepared.content_ui,
+        }
+    }
+}
+
+impl Container {
+    fn new(kind: ContainerKind) -> Self {
+        Self { kind, guard: None, entered: None }
+    }
+
+    fn child_ui(&mut self) -> Option<&mut egui::Ui> {
+        self.entered.as_mut().map(EnteredContainer::child_ui)
+    }
+}
+
+fn horizontal_layout(ui: &egui::Ui, align: egui::Align) -> egui::Layout {
+    if ui.layout().prefer_right_to_left() {
+      egui::Layout::right_to_left(align)
+    } else {
+      egui::Layout::left_to_right(align)
+    }
+}
+
+// allocates exactly size in the parent, a single widget in the child fills it
+fn sized_child(ui: &mut egui::Ui, size: egui::Vec2) -> egui::Ui {
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let layout = egui::Layout::centered_and_justified(ui.layout().main_dir());
+
+    ui.new_child(egui::UiBuilder::new().max_rect(rect).layout(layout))
+}
+
+fn indented_child(ui: &mut egui::Ui, builder: egui::UiBuilder) -> Box<egui::Ui> {
+    let mut child_rect = ui.available_rect_before_wrap();
+    child_rect.min.x += ui.spacing().indent;
+
+    Box::new(ui.new_child(builder.max_rect(child_rect)))
+}
+
+#[pymethods]
+impl Container {
+
+    unsafe fn __enter__(&mut self, py: Python<'_>) -> PyResult<Option<bool>> {
+        if self.entered.is_some() {
+          return Err(PyRuntimeError::new_err(CONTAINER_ERR));
+        }
+
+        let ui = current_ui()?;
+        let mut open = None;
+
+        let entered = match &self.kind {
+          ContainerKind::Layout(layout, height) => {
+            let mut rect = ui.available_rect_before_wrap();
+            if let Some(height) = height {
+              rect.max.y = rect.min.y + height;
+            }
+
+            EnteredContainer::Child(Box::new(ui.new_child(egui::UiBuilder::new().max_rect(rect).layout(*layout))))
+          },
+          ContainerKind::Indent => EnteredContainer::Indent(indented_child(ui, egui::UiBuilder::new())),
+          ContainerKind::Group => EnteredContainer::Frame(Box::new(egui::Frame::group(ui.style()).begin(ui))),
+          ContainerKind::Frame(frame) => EnteredContainer::Frame(Box::new(frame.begin(ui))),
+          ContainerKind::Scope => EnteredContainer::Child(Box::new(ui.new_child(egui::UiBuilder::new()))),
+          ContainerKind::PushId(id) => EnteredContainer::Child(Box::new(ui.new_child(egui::UiBuilder::new().id_salt(id)))),
+          ContainerKind::Sized(size) => EnteredContainer::Allocated(Box::new(sized_child(ui, *size))),
+          ContainerKind::Collapsing(CollapsingOptions { heading, default_open, id_salt, open: open_bool }) => {
+            let id = ui.make_persistent_id(id_salt.as_ref().unwrap_or(heading));
+            let mut state = egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, *default_open);
+            if let Some(open_bool) = open_bool {
+              if open_bool.borrow(py).value != state.is_open() {
+                state.toggle(ui);
+              }
+            }
+
+            let header = ui.horizontal(|ui| {
+              state.show_toggle_button(ui, egui::collapsing_header::paint_default_icon);
+              ui.add(egui::Label::new(heading.as_str()).selectable(false).sense(egui::Sense::click()))
+            }).inner;
+            if header.clicked() {
+              state.toggle(ui);
+            }
+            state.store(ui.ctx());
+            open = Some(state.is_open());
+            if let Some(open_bool) = open_bool {
+              open_bool.borrow_mut(py).value = state.is_open();
+            }
+
+            if state.is_open() {
+              EnteredContainer::Indent(indented_child(ui, egui::UiBuilder::new().id_salt(id)))
+            } else {
+              EnteredContainer::Hidden(indented_child(ui, egui::UiBuilder::new().id_salt(id).invisible()))
+            }
+          },
+        };
+
+        self.guard = Some(push_ui(self.entered.insert(entered).child_ui()));
+
+        Ok(open)
+    }
+
+    #[pyo3(signature = (*_args))]
+    unsafe fn __exit__(&mut self, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<bool> {
+        let child: *mut egui::Ui = self.child_ui().ok_or_else(|| PyRuntimeError::new_err(CONTAINER_ERR))?;
+        let is_last = UI_STACK.with_borrow(|stack| stack.last() == Some(&child));
+
+        if !is_last {
+          return Err(PyRuntimeError::new_err(CONTAINER_ERR));
+        }
+        self.guard = None;
+
+        let ui = current_ui()?;
+
+        match self.entered.take().expect("the container is entered, since its ui was on the stack") {
+          EnteredContainer::Child(child) => {
+            ui.advance_cursor_after_rect(child.min_rect());
+          },
+          EnteredContainer::Indent(child) => {
+            if ui.visuals().indent_has_left_vline {
+              let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+              let x = child.max_rect().min.x - 0.5 * ui.spacing().indent;
+              let top = child.max_rect().min.y;
+              let bottom = child.min_rect().bottom() - 2.0;
+
+              ui.painter().line_segment([egui::pos2(x, top), egui::pos2(x, bottom)], stroke);
+            }
+
+            ui.allocate_rect(child.min_rect(), egui::Sense::hover());
+          },
+          EnteredContainer::Frame(prepared) => {
+            prepared.end(ui);
+          },
+          EnteredContainer::Hidden(_) | EnteredContainer::Allocated(_) => (),
+        }
+
+        // exceptions raised inside of the with statement are not suppressed
+        Ok(false)
+    }
+}
+
+/// How with_layout places widgets: the direction, the alignment along it (main) and across it (cross).
+///
+/// Alignments are "min" (left or top), "center" and "max" (right or bottom).
+///
+/// Args:
+///     direction (str): "top_down", "bottom_up", "left_to_right" or "right_to_left". Default is "top_down"
+///
+///     main_align (str): alignment along the direction. Default is "min"
+///
+///     cross_align (str): alignment across the direction. Default is "min"
+///
+///     main_wrap (bool): start a new row or column when there is no space left. Default is False
+///
+///     main_justify (bool): stretch widgets along the direction. Default is False
+///
+///     cross_justify (bool): stretch widgets across the direction. Default is False
+///
+/// Usage::
+///
+///     with with_layout(Layout.right_to_left()):
+///       button("Cancel")
+///       button("OK")
+///
+///     with with_layout(Layout("bottom_up", cross_align="center")):
+///       label("Status: ready")
+#[pyclass(frozen)]
+#[derive(Clone, Copy)]
+struct Layout(egui::Layout);
+
+#[pymethods]
+impl Layout {
+    #[new]
+    #[pyo3(signature = (direction = "top_down", main_align = "min", cross_align = "min", main_wrap = false, main_justify = false, cross_justify = false))]
+    fn new(direction: &str, main_align: &str, cross_align: &str, main_wrap: bool, main_justify: bool, cross_justify: bool) -> PyResult<Self> {
+        let direction = match direction {
+          "top_down" => egui::Direction::TopDown,
+          "bottom_up" => egui::Direction::BottomUp,
+          "left_to_right" => egui::Direction::LeftToRight,
+          "right_to_left" => egui::Direction::RightToLeft,
+          other => return Err(PyValueError::new_err(format!("Unknown direction '{}'", other)))
+        };
+
+        Ok(Layout(egui::Layout::from_main_dir_and_cross_align(direction, extract_align(cross_align)?)
+          .with_main_align(extract_align(main_align)?)
+          .with_main_wrap(main_wrap)
+          .with_main_justify(main_justify)
+          .with_cross_justify(cross_justify)))
+    }
+
+    /// Widgets go down, aligned across by cross_align
+    #[staticmethod]
+    #[pyo3(signature = (cross_align = "min"))]
+    fn top_down(cross_align: &str) -> PyResult<Self> {
+        Ok(Layout(egui::Layout::top_down(extract_align(cross_align)?)))
+    }
+
+    /// Widgets go up from the bottom, aligned across by cross_align
+    #[staticmethod]
+    #[pyo3(signature = (cross_align = "min"))]
+    fn bottom_up(cross_align: &str) -> PyResult<Self> {
+        Ok(Layout(egui::Layout::bottom_up(extract_align(cross_align)?)))
+    }
+
+    /// Widgets go right, aligned across by cross_align
+    #[staticmethod]
+    #[pyo3(signature = (cross_align = "min"))]
+    fn left_to_right(cross_align: &str) -> PyResult<Self> {
+        Ok(Layout(egui::Layout::left_to_right(extract_align(cross_align)?)))
+    }
+
+    /// Widgets go left from the right edge, aligned across by cross_align
+    #[staticmethod]
+    #[pyo3(signature = (cross_align = "min"))]
+    fn right_to_left(cross_align: &str) -> PyResult<Self> {
+        Ok(Layout(egui::Layout::right_to_left(extract_align(cross_align)?)))
+    }
+
+    /// A single widget in the center, stretched to the available space
+    #[staticmethod]
+    fn centered_and_justified() -> Self {
+        Layout(egui::Layout::centered_and_justified(egui::Direction::TopDown))
+    }
+}
+
+/// A series of points connected by a line, drawn by plot
+///
+/// Args:
+///     points (list[tuple[float, float]] | numpy.ndarray): (x, y) points of the line. Arrays must have (N, 2) shape and a float or integer dtype
+///
+///     name (str): name of the line shown in the legend
+///
+///     color (Color | str | tuple[int, int, int]): color of the line
+///
+///     width (float): stroke width of the line
+///
+/// Usage::
+///
+///     sin = Line([(x / 10, math.sin(x / 10)) for x in range(100)], name="sin")
+///     plot("my_plot", [sin])
+#[pyclass]
+struct Line {
+    points: Vec<egui_plot::PlotPoint>,
+    name: Option<String>,
+    color: Option<egui::Color32>,
+    width: Option<f32>,
+}
+
+#[pymethods]
+impl Line {
+    #[new]
+    #[pyo3(signature = (points, **kwargs))]
+    fn new(points: &Bound<'_, PyAny>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut line = Line {
+            points: extract_plot_points(points)?,
+            name: None,
+            color: None,
+            width: None,
+        };
+
+        if let Some(kwargs) = kwargs {
+
+            if let Some(name) = kwargs.get_item("name")? {
+                line.name = Some(name.downcast::<PyString>()?.extract()?);
+            }
+
+            if let Some(color) = kwargs.get_item("color")? {
+                line.color = Some(extract_color(&color)?);
+            }
+
+            if let Some(width) = kwargs.get_item("width")? {
+                line.width = Some(width.extract()?);
+            }
+        }
+
+        Ok(line)
+    }
+
+    /// Replace the points of the line
+    fn set_points(&mut self, points: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.points = extract_plot_points(points)?;
+        Ok(())
+    }
+}
+
+impl Line {
+    fn to_plot_item(&self) -> egui_plot::Line<'_> {
+        let mut line = egui_plot::Line::new(egui_plot::PlotPoints::Borrowed(&self.points));
+
+        if let Some(name) = &self.name {
+            line = line.name(name);
+        }
+        if let Some(color) = self.color {
+            line = line.color(color);
+        }
+        if let Some(width) = self.width {
+            line = line.width(width);
+        }
+
+        line
+    }
+}
+
+/// A set of points drawn as markers by plot, e.g. a scatter plot
+///
+/// Args:
+///     points (list[tuple[float, float]] | numpy.ndarray): (x, y) coordinates of the points. Arrays must have (N, 2) shape and a float or integer dtype
+///
+///     name (str): name of the series shown in the legend
+///
+///     color (Color | str | tuple[int, int, int]): color of the markers
+///
+///     radius (float): radius of the markers
+///
+///     shape (str): one of "circle", "diamond", "square", "cross", "plus", "up", "down", "left", "right", "asterisk"
+///
+///     filled (bool): whether the markers are filled. Default is True
+///
+/// Usage::
+///
+///     measurements = Points([(1, 2.5), (2, 3.1), (3, 2.9)], shape="diamond", radius=4)
+///     plot("my_plot", [measurements])
+#[pyclass]
+struct Points {
+    points: Vec<egui_plot::PlotPoint>,
+    name: Option<String>,
+    color: Option<egui::Color32>,
+    radius: Option<f32>,
+    shape: Option<egui_plot::MarkerShape>,
+    filled: Option<bool>,
+}
+
+#[pymethods]
+impl Points {
+    #[new]
+    #[pyo3(signature = (points, **kwargs))]
+    fn new(points: &Bound<'_, PyAny>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut p = Points {
+            points: extract_plot_points(points)?,
+            name: None,
+            color: None,
+            radius: None,
+            shape: None,
+            filled: None,
+        };
+
+        if let Some(kwargs) = kwargs {
+
+            if let Some(name) = kwargs.get_item("name")? {
+                p.name = Some(name.downcast::<PyString>()?.extract()?);
+            }
+
+            if let Some(color) = kwargs.get_item("color")? {
+                p.color = Some(extract_color(&color)?);
+            }
+
+            if let Some(radius) = kwargs.get_item("radius")? {
+                p.radius = Some(radius.extract()?);
+            }
+
+            if let Some(shape) = kwargs.get_item("shape")? {
+                p.shape = Some(marker_shape(shape.downcast::<PyString>()?.to_str()?)?);
+            }
+
+            if let Some(filled) = kwargs.get_item("filled")? {
+                p.filled = Some(filled.downcast::<PyBool>()?.extract()?);
+            }
+        }
+
+        Ok(p)
+    }
+
+    /// Replace the points of the series
+    fn set_points(&mut self, points: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.points = extract_plot_points(points)?;
+        Ok(())
+    }
+}
+
+impl Points {
+    fn to_plot_item(&self) -> egui_plot::Points<'_> {
+        let mut p = egui_plot::Points::new(egui_plot::PlotPoints::Borrowed(&self.points));
+
+        if let Some(name) = &self.name {
+            p = p.name(name);
+        }
+        if let Some(color) = self.color {
+            p = p.color(color);
+        }
+        if let Some(radius) = self.radius {
+            p = p.radius(radius);
+        }
+        if let Some(shape) = self.shape {
+            p = p.shape(shape);
+        }
+        if let Some(filled) = self.filled {
+            p = p.filled(filled);
+        }
+
+        p
+    }
+}
+
+/// A bar chart drawn by plot
+///
+/// Args:
+///     values (list[float] | numpy.ndarray): height of every bar
+///
+///     positions (list[float] | numpy.ndarray): position of every bar on the axis. Defaults to 0, 1, 2, ...
+///
+///     labels (list[str]): name of every bar shown on hover
+///
+///     name (str): name of the chart shown in the legend
+///
+///     color (Color | str | tuple[int, int, int]): color of the bars
+///
+///     width (float): width of the bars. Default is 0.5
+///
+///     horizontal (bool): draw bars from left to right instead of bottom to top
+///
+///     stack_on (list[BarChart]): draw the bars on top of these charts
+///
+/// Usage::
+///
+///     apples = BarChart([3, 5, 2], labels=["Mon", "Tue", "Wed"], name="apples")
+///     pears = BarChart([1, 2, 4], name="pears", color=(0, 200, 0), stack_on=[apples])
+///     plot("fruits", [apples, pears])
+#[pyclass]
+struct BarChart {
+    values: Vec<f64>,
+    positions: Option<Vec<f64>>,
+    labels: Option<Vec<String>>,
+    name: Option<String>,
+    color: Option<egui::Color32>,
+    width: Option<f64>,
+    horizontal: bool,
+    stack_on: Vec<Py<BarChart>>,
+}
+
+#[pymethods]
+impl BarChart {
+    #[new]
+    #[pyo3(signature = (values, **kwargs))]
+    fn new(values: &Bound<'_, PyAny>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut chart = BarChart {
+            values: extract_values(values)?,
+            positions: None,
+            labels: None,
+            name: None,
+            color: None,
+            width: None,
+            horizontal: false,
+            stack_on: Vec::new(),
+        };
+
+        if let Some(kwargs) = kwargs {
+
+            if let Some(positions) = kwargs.get_item("positions")? {
+                chart.positions = Some(extract_values(&positions)?);
+            }
+
+            if let Some(labels) = kwargs.get_item("labels")? {
+                chart.labels = Some(labels.extract()?);
+            }
+
+            if let Some(name) = kwargs.get_item("name")? {
+                chart.name = Some(name.downcast::<PyString>()?.extract()?);
+            }
+
+            if let Some(color) = kwargs.get_item("color")? {
+                chart.color = Some(extract_color(&color)?);
+            }
+
+            if let Some(width) = kwargs.get_item("width")? {
+                chart.width = Some(width.extract()?);
+            }
+
+            if let Some(horizontal) = kwargs.get_item("horizontal")? {
+                chart.horizontal = horizontal.downcast::<PyBool>()?.extract()?;
+            }
+
+            if let Some(stack_on) = kwargs.get_item("stack_on")? {
+                chart.stack_on = stack_on.extract()?;
+            }
+        }
+
+        Ok(chart)
+    }
+
+    /// Replace the heights of the bars
+    fn set_values(&mut self, values: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.values = extract_values(values)?;
+        Ok(())
+    }
+}
+
+impl BarChart {
+    fn to_plot_item(&self, py: Python<'_>) -> egui_plot::BarChart {
+        let bars = self.values.iter().enumerate().map(|(i, value)| {
+            let position = self.positions.as_ref()
+              .and_then(|p| p.get(i).copied())
+              .unwrap_or(i as f64);
+            let mut bar = egui_plot::Bar::new(position, *value);
+
+            if let Some(label) = self.labels.as_ref().and_then(|l| l.get(i)) {
+                bar = bar.name(label);
+            }
+            bar
+        }).collect();
+
+        let mut chart = egui_plot::BarChart::new(bars);
+
+        if let Some(name) = &self.name {
+            chart = chart.name(name);
+        }
+        if let Some(color) = self.color {
+            chart = chart.color(color);
+        }
+        if let Some(width) = self.width {
+            chart = chart.width(width);
+        }
+        if self.horizontal {
+            chart = chart.horizontal();
+        }
+        if !self.stack_on.is_empty() {
+            let bases: Vec<egui_plot::BarChart> = self.stack_on.iter()
+              .map(|base| base.borrow(py).to_plot_item(py))
+              .collect();
+            chart = chart.stack_on(&bases.iter().collect::<Vec<_>>());
+        }
+
+        chart
+    }
+}
+
+/// What the user did with a plot this frame. Returned by plot
+///
+/// Usage::
+///
+///     r = plot("my_plot", [measurements])
+///     if r.pointer is not None:
+///       label(f"x: {r.pointer[0]:.2f} y: {r.pointer[1]:.2f}")
+///     if r.clicked_pos is not None and r.nearest is not None:
+///       item, index = r.nearest
+///       print(f"clicked near point {index} of item {item}")
+#[pyclass]
+struct PlotResponse {
+    /// The Response of the plot widget
+    #[pyo3(get)]
+    response: Response,
+    /// Position of the pointer in plot coordinates if the plot is hovered
+    #[pyo3(get)]
+    pointer: Option<(f64, f64)>,
+    /// Position of the click in plot coordinates if the plot was clicked this frame
+    #[pyo3(get)]
+    clicked_pos: Option<(f64, f64)>,
+    /// (item index, point index) of the Line or Points point closest to the pointer
+    #[pyo3(get)]
+    nearest: Option<(usize, usize)>,
+    /// Currently shown (x_min, x_max, y_min, y_max)
+    #[pyo3(get)]
+    bounds: (f64, f64, f64, f64),
+}
+
+enum PlotItem<'py> {
+    Line(PyRef<'py, Line>),
+    Points(PyRef<'py, Points>),
+    BarChart(PyRef<'py, BarChart>),
+}
+
+// Start function
+
+struct PyeguiApp<'py> {
+    update_func: Py<PyAny>,
+    state: PersistentState<'py>,
+    // whether egui memory, e.g. open collapsing headers, is saved
+    persist_memory: bool,
+}
+
+// what happens when update_func raises
+#[derive(Clone, Copy, PartialEq)]
+enum ErrorPolicy {
+    // close the app and re-raise the exception from run_native
+    Raise,
+    // print the exception and keep running
+    Log,
+    // keep running
+    Ignore,
+}
+
+// passes the exception raised by update_func to the error handler or applies the policy
+fn handle_app_error(py: Python<'_>, ctx: &egui::Context, err: PyErr) {
+  let handler = ERROR_HANDLER.lock().unwrap().as_ref().map(|h| h.clone_ref(py));
+
+  let err = match handler {
+    Some(handler) => match handler.call1(py, (err.value(py),)) {
+      Ok(_) => return,
+      // the handler decided to crash
+      Err(err) => err
+    },
+    None => match *ERROR_POLICY.lock().unwrap() {
+      ErrorPolicy::Raise => err,
+      ErrorPolicy::Log => return log_error(py, &err),
+      ErrorPolicy::Ignore => return,
+    }
+  };
+
+  *APP_ERROR.lock().unwrap() = Some(err);
+  ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+}
+
+// prints the exception and shows it in the error panel
+fn log_error(py: Python<'_>, err: &PyErr) {
+  err.display(py);
+
+  let traceback = py.import("traceback")
+    .and_then(|tb| tb.call_method1("format_exception", (err.value(py),)))
+    .and_then(|lines| lines.extract::<Vec<String>>())
+    .map(|lines| lines.concat())
+    .unwrap_or_else(|_| err.to_string());
+
+  *LAST_ERROR.lock().unwrap() = Some(traceback);
+  request_app_repaint();
+}
+
+fn show_error_panel(ctx: &egui::Context) {
+  let mut last_error = LAST_ERROR.lock().unwrap();
+  let Some(traceback) = last_error.as_ref() else {
+    return;
+  };
+  let mut dismissed = false;
+
+  egui::TopBottomPanel::bottom("pyegui_error_panel").show(ctx, |ui| {
+    let summary = traceback.lines().last().unwrap_or("Exception");
+    let color = ui.visuals().error_fg_color;
+
+    egui::CollapsingHeader::new(egui::RichText::new(summary).color(color)).show(ui, |ui| {
+      egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+        ui.label(egui::RichText::new(traceback.as_str()).monospace());
+      });
+    });
+
+    ui.horizontal(|ui| {
+      if ui.button("Copy").clicked() {
+        ctx.copy_text(traceback.clone());
+      }
+      dismissed = ui.button("Dismiss").clicked();
+    });
+  });
+
+  if dismissed {
+    *last_error = None;
+  }
+}
+
+// sent with ViewportCommand::Screenshot and returned in Event::Screenshot
+struct ScreenshotRequest {
+    callback: Py<PyAny>,
+    // png bytes or a numpy array
+    png: bool,
+}
+
+impl ScreenshotRequest {
+  fn send(&self, py: Python<'_>, image: &egui::ColorImage) -> PyResult<()> {
+    let [w, h] = image.size;
+    let pixels: Vec<u8> = image.pixels.iter().flat_map(|c| c.to_srgba_unmultiplied()).collect();
+
+    let value = if self.png {
+      let mut png = std::io::Cursor::new(Vec::new());
+      ::image::RgbaImage::from_raw(w as u32, h as u32, pixels)
+        .expect("screenshot size should match its pixels")
+        .write_to(&mut png, ::image::ImageFormat::Png)
+        .map_err(|e| PyRuntimeError::new_err(format!("Cannot encode the screenshot: {}", e)))?;
+
+      PyBytes::new(py, png.get_ref()).into_any()
+    } else {
+      py.import("numpy")?
+        .call_method1("frombuffer", (PyBytes::new(py, &pixels), "uint8"))?
+        .call_method1("reshape", ((h, w, 4),))?
+        .call_method0("copy")?
+    };
+
+    self.callback.call1(py, (value,))?;
+    Ok(())
+  }
+}
+
+// calls the callbacks of Context.screenshot
+fn send_screenshots(ctx: &egui::Context) {
+  let screenshots: Vec<_> = ctx.input(|i| i.events.iter().filter_map(|e| match e {
+    egui::Event::Screenshot { user_data, image, .. } => user_data.data.clone().map(|data| (data, image.clone())),
+    _ => None
+  }).collect());
+
+  for (data, image) in screenshots {
+    if let Some(request) = data.downcast_ref::<ScreenshotRequest>() {
+      Python::with_gil(|py| {
+        if let Err(err) = request.send(py, &image) {
+          handle_app_error(py, ctx, err);
+        }
+      });
+    }
+  }
+}
+
+// what is written to eframe storage when the app is saved
+enum PersistentState<'py> {
+    None,
+    Dict(Bound<'py, PyDict>),
+    SaveFunc(Bound<'py, PyAny>),
+}
+
+impl eframe::App for PyeguiApp<'_> {
+  fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+
+    let ctx_r = Context(ctx.clone());
+
+    if !ACCESSIBILITY.load(Ordering::Relaxed) {
+      ctx.disable_accesskit();
+    }
+
+    if FONTS_CHANGED.swap(false, Ordering::Relaxed) {
+      if let Some(fonts) = FONTS.lock().unwrap().as_ref() {
+        ctx.set_fonts(fonts.clone());
+      }
+    }
+
+    show_error_panel(ctx);
+    send_screenshots(ctx);
+
+    egui::CentralPanel::default().show(ctx, |ui| {
+
+      debug!("Push UI");
+      let _guard = push_ui(ui);
+
+      // the app is closing because of an exception
+      if APP_ERROR.lock().unwrap().is_some() {
+        return;
+      }
+
+      debug!("Execute update_func");
+
+      Python::with_gil(|py| {
+        if let Err(err) = self.update_func.call1(py, (ctx_r,)) {
+          handle_app_error(py, ctx, err);
+        }
+      });
+
+      debug!("Executed update_func");
+    });
+
+    show_toasts(ctx);
+
+    if ctx.data(|d| d.get_temp(egui::Id::new(CONTINUOUS_KEY)).unwrap_or(false)) {
+      ctx.request_repaint();
+    }
+  }
+
+  fn persist_egui_memory(&self) -> bool {
+    self.persist_memory
+  }
+
+  fn save(&mut self, storage: &mut dyn eframe::Storage) {
+    let state = match &self.state {
+      PersistentState::None => return,
+      PersistentState::Dict(dict) => Ok(dict.clone().into_any()),
+      PersistentState::SaveFunc(save_func) => save_func.call0(),
+    };
+
+    let json = state.and_then(|state| {
+      state.py().import("json")?.call_method1("dumps", (state,))?.extract::<String>()
+    });
+
+    match json {
+      Ok(json) => storage.set_string(STATE_STORAGE_KEY, json),
+      Err(err) => Python::with_gil(|py| err.display(py)),
+    }
+  }
+}
+
+// restores the state saved by PyeguiApp::save
+fn load_state(storage: Option<&dyn eframe::Storage>, state: &PersistentState<'_>, load_func: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+  let Some(json) = storage.and_then(|storage| storage.get_string(STATE_STORAGE_KEY)) else {
+    return Ok(());
+  };
+
+  let py = match (state, load_func) {
+    (PersistentState::Dict(dict), _) => dict.py(),
+    (_, Some(load_func)) => load_func.py(),
+    _ => return Ok(()),
+  };
+  let value = py.import("json")?.call_method1("loads", (json,))?;
+
+  if let PersistentState::Dict(dict) = state {
+    dict.call_method1("update", (&value,))?;
+  }
+
+  if let Some(load_func) = load_func {
+    load_func.call1((value,))?;
+  }
+
+  Ok(())
+}
+
+
+/// Creates a window and runs update_func.
+/// This is an entrypoint for your GUI application.
+/// 
+/// Args:
+///     app_name (str): name displayed at the header bar
+///
+///     update_func (Callable[[Context], None]): your function that draws UI
+///
+///     inner_height (float): the desired height of the window
+///
+///     inner_width (float): the desired width of the window
+///
+///     min_inner_height (float): min height of the window
+///
+///     min_inner_width (float): min width of the window
+///
+///     max_inner_height (float): max height of the window
+///
+///     max_inner_width (float): max width of the window
+///
+///     fullscreen (bool): whether to open app in fullscreen
+///
+///     maximized (bool): whether to open app maximized
+///
+///     resizable (bool): whether our app is resizable
+///
+///     transparent (bool): whether our app is transparent
+///
+///     icon_path (str): path to icon in rgba format
+///
+///     icon (bytes | str | os.PathLike): png icon as bytes or a path to it
+///
+///     state (dict): dict that is restored on start and saved on exit and every 30 seconds. Values should be JSON serializable
+///
+///     save_fun (Callable[[], Any]): your function that returns the JSON serializable state to save on exit and every 30 seconds.
+///       It can't be passed together with state
+///
+///     load_fun (Callable[[Any], None]): your function that receives the state saved previously
+///
+///     continuous (bool): call update_func every frame instead of only when something happens. Default is False
+///
+///     persist_window (bool): restore the position and size of the window from the last run.
+///       The restored size overrides inner_width and inner_height. Default is False
+///
+///     persist_memory (bool): restore the state of the UI from the last run, e.g. which collapsing headers are open,
+///       the theme and the zoom factor. It overrides options like default_open of collapsing. Default is False
+///
+///     on_error (str): what to do when update_func raises. "raise" closes the app and raises the exception from run_native,
+///       "log" prints the exception, shows it in a panel at the bottom of the window and keeps the app running,
+///       "ignore" keeps the app running silently. Default is "raise".
+///       See also set_error_handler
+/// 
+/// Examples::
+///
+///     name = Str("")
+///     
+///     def update_func(ctx):
+///         heading(f"Hello, {name.value}!")
+///         text_edit_singleline(name)
+///         
+///         if button_clicked("click me"):
+///             print("clicked")
+///     
+///     run_native("My app", update_func)
+///
+///     # settings survive restarts
+///     settings = {"volume": 50}
+///
+///     run_native("My app", update_func, state=settings)
+///
+#[pyfunction]
+#[pyo3(signature = (app_name, update_func, **kwargs))]
+unsafe fn run_native(
+    app_name: &str,
+    update_func: Bound<'_, PyAny>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+  debug!("Trying to get the app lock");
+  // ensure thread safety 
+  let _lock = APP_MUTEX.try_lock().map_err(|_| PyRuntimeError::new_err(APP_MUTEX_ERR))?;
+  // parse kwargs
+  let mut viewport = egui::viewport::ViewportBuilder::default();
+  let mut state = PersistentState::None;
+  let mut load_func = None;
+  let mut continuous = false;
+  let mut persist_window = false;
+  let mut persist_memory = false;
+  let mut policy = ErrorPolicy::Raise;
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(dict) = kwargs.get_item("state")? {
+      state = PersistentState::Dict(dict.downcast_into::<PyDict>()?);
+    }
+
+    if let Some(save_func) = kwargs.get_item("save_fun")? {
+      if let PersistentState::Dict(_) = state {
+        return Err(PyTypeError::new_err("state and save_fun can't be passed together"));
+      }
+      state = PersistentState::SaveFunc(save_func);
+    }
+
+    load_func = kwargs.get_item("load_fun")?;
+
+    if let Some(value) = kwargs.get_item("continuous")? {
+      continuous = value.downcast::<PyBool>()?.extract()?;
+    }
+
+    if let Some(value) = kwargs.get_item("persist_window")? {
+      persist_window = value.downcast::<PyBool>()?.extract()?;
+    }
+
+    if let Some(value) = kwargs.get_item("persist_memory")? {
+      persist_memory = value.downcast::<PyBool>()?.extract()?;
+    }
+
+    if let Some(on_error) = kwargs.get_item("on_error")? {
+      policy = match on_error.downcast::<PyString>()?.to_str()? {
+        "raise" => ErrorPolicy::Raise,
+        "log" => ErrorPolicy::Log,
+        "ignore" => ErrorPolicy::Ignore,
+        other => return Err(PyValueError::new_err(format!("Unknown on_error '{}'", other)))
+      };
+    }
+
+    if let (Some(height), Some(width)) = (kwargs.get_item("inner_height")?, kwargs.get_item("inner_width")?) {
+      viewport = viewport.with_inner_size([
+        width.downcast::<PyInt>()?.extract()?,
+        height.downcast::<PyInt>()?.extract()? 
+      ]); 
+    }
+
+    if let (Some(height), Some(width)) = (kwargs.get_item("min_inner_height")?, kwargs.get_item("min_inner_width")?) {
+      viewport = viewport.with_min_inner_size([
+        width.downcast::<PyInt>()?.extract()?,
+        height.downcast::<PyInt>()?.extract()? 
+      ]); 
+    }
+
+    if let (Some(height), Some(width)) = (kwargs.get_item("max_inner_height")?, kwargs.get_item("max_inner_width")?) {
+      viewport = viewport.with_max_inner_size([
+        width.downcast::<PyInt>()?.extract()?,
+        height.downcast::<PyInt>()?.extract()? 
+      ]); 
+    }
+
+    if let Some(fullscreen) = kwargs.get_item("fullscreen")? {
+      viewport = viewport.with_fullscreen(fullscreen.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(maximized) = kwargs.get_item("maximized")? {
+      viewport = viewport.with_maximized(maximized.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(resizable) = kwargs.get_item("resizable")? {
+      viewport = viewport.with_resizable(resizable.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(transparent) = kwargs.get_item("transparent")? {
+      viewport = viewport.with_transparent(transparent.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(icon_path) = kwargs.get_item("icon_path")? {
+      let path = icon_path.downcast::<PyString>()?.extract::<String>()?;
+      let buf = fs::read(path)?;
+
+      viewport = viewport.with_icon(decode_icon(&buf)?);
+    }
+
+    if let Some(icon) = kwargs.get_item("icon")? {
+      let buf = match icon.downcast::<PyBytes>() {
+        Ok(bytes) => bytes.as_bytes().to_vec(),
+        Err(_) => fs::read(icon.extract::<std::path::PathBuf>()?)?
+      };
+
+      viewport = viewport.with_icon(decode_icon(&buf)?);
+    }
+  }
+
+  *ERROR_POLICY.lock().unwrap() = policy;
+  *APP_ERROR.lock().unwrap() = None;
+  *LAST_ERROR.lock().unwrap() = None;
+
+  let options = eframe::NativeOptions {
+    viewport,
+    persist_window,
+    ..eframe::NativeOptions::default()
+  };
+  debug!("Creating a window");
+  // create a window
+  let result = eframe::run_native(
+        app_name,
+        options,
+        Box::new(|cc| {
+            // This gives us image support:
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            // toasts and messages sent from other threads should wake the app up
+            *APP_CTX.lock().unwrap() = Some(cc.egui_ctx.clone());
+            if let Some(fonts) = FONTS.lock().unwrap().as_ref() {
+              cc.egui_ctx.set_fonts(fonts.clone());
+            }
+            cc.egui_ctx.data_mut(|d| d.insert_temp(egui::Id::new(CONTINUOUS_KEY), continuous));
+            // eframe restores the memory saved by a previous run even if it is not persisted anymore
+            if !persist_memory {
+              cc.egui_ctx.memory_mut(|m| *m = egui::Memory::default());
+            }
+
+            if let Err(err) = load_state(cc.storage, &state, load_func.as_ref()) {
+              Python::with_gil(|py| err.display(py));
+            }
+
+            Ok(Box::new(PyeguiApp { update_func: update_func.unbind(), state, persist_memory }))
+        }),
+  );
+
+  if let Some(err) = APP_ERROR.lock().unwrap().take() {
+    return Err(err);
+  }
+
+  match result {
+    Ok(_) => Ok(()),
+    Err(err) => Err(PyRuntimeError::new_err(format!("Cannot create a window: {}", err)))
+  }
+}
+
+/// Set a function that is called with the exception when update_func raises. It replaces the on_error option of run_native.
+///
+/// The handler is called inside the frame, so it can show widgets. If the handler raises, the app is closed and
+/// run_native raises that exception. Pass None to remove the handler.
+///
+/// The handler receives every exception, including the ones raised inside nested functions, e.g. of group or collapsing.
+/// The rest of the frame is skipped after an exception.
+///
+/// Example::
+///
+///     def on_error(e):
+///         toast_error(f"Something went wrong: {e}")
+///         if isinstance(e, MemoryError):
+///             raise e
+///
+///     set_error_handler(on_error)
+///     run_native("My app", update_func)
+#[pyfunction]
+#[pyo3(signature = (handler))]
+fn set_error_handler(handler: Option<Py<PyAny>>) {
+  *ERROR_HANDLER.lock().unwrap() = handler;
+}
+
+/// Turn accessibility support on or off. It is on by default.
+///
+/// pyegui describes the UI to screen readers with AccessKit. The description is built only when
+/// a screen reader is running, so there is no cost otherwise.
+///
+/// Args:
+///     enabled (bool): False stops describing the UI to screen readers
+#[pyfunction]
+fn set_accessibility(enabled: bool) {
+  ACCESSIBILITY.store(enabled, Ordering::Relaxed);
+
+  if enabled {
+    if let Some(ctx) = APP_CTX.lock().unwrap().as_ref() {
+      ctx.enable_accesskit();
+    }
+  }
+}
+
+/// Load a .ttf or .otf font. It can be called before run_native or while the app runs.
+///
+/// The default fonts only support latin and cyrillic alphabets, so add a font to show e.g. Japanese or Arabic.
+/// The font is put first in the family, so it is used for all the glyphs it has. The other glyphs come from the default fonts.
+/// ValueError is raised if the data is not a valid font.
+///
+/// Args:
+///     name (str): name of the font, used by set_font_family
+///
+///     source (str | bytes): path to the font file or its content
+///
+///     family (str | None): "proportional" or "monospace" to use the font for that family. Default is "proportional".
+///       None only loads the font, use set_font_family to use it
+///
+/// Example::
+///
+///     add_font("NotoSansJP", "NotoSansJP-Regular.ttf")
+///     add_font("JetBrainsMono", "JetBrainsMono-Regular.ttf", family="monospace")
+///     run_native("My app", update_func)
+#[pyfunction]
+#[pyo3(signature = (name, source, family = Some("proportional")))]
+fn add_font(name: String, source: &Bound<'_, PyAny>, family: Option<&str>) -> PyResult<()> {
+  let buf = match source.downcast::<PyBytes>() {
+    Ok(bytes) => bytes.as_bytes().to_vec(),
+    Err(_) => fs::read(source.extract::<std::path::PathBuf>()?)?
+  };
+  let family = family.map(font_family).transpose()?;
+
+  // epaint panics on invalid fonts when they are applied, so they are checked here
+  if let Err(err) = ab_glyph::FontRef::try_from_slice(&buf) {
+    return Err(PyValueError::new_err(format!("Font '{}' is not a valid TTF/OTF font: {}", name, err)));
+  }
+
+  let mut fonts = FONTS.lock().unwrap();
+  let fonts = fonts.get_or_insert_with(FontDefinitions::default);
+
+  fonts.font_data.insert(name.clone(), Arc::new(FontData::from_owned(buf)));
+  if let Some(family) = family {
+    let names = fonts.families.entry(family).or_default();
+    names.retain(|n| *n != name);
+    names.insert(0, name);
+  }
+
+  FONTS_CHANGED.store(true, Ordering::Relaxed);
+  request_app_repaint();
+  Ok(())
+}
+
+/// Set the fonts of a family. Glyphs are taken from the first font that has them.
+///
+/// The default fonts are "Ubuntu-Light", "Hack", "NotoEmoji-Regular" and "emoji-icon-font".
+///
+/// Args:
+///     family (str): "proportional" or "monospace"
+///
+///     fonts (list[str]): names of the fonts, either the default fonts or fonts loaded with add_font
+///
+/// Example::
+///
+///     add_font("Inter", "Inter-Regular.ttf", family=None)
+///     set_font_family("proportional", ["Inter", "NotoEmoji-Regular", "emoji-icon-font"])
+#[pyfunction]
+fn set_font_family(family: &str, fonts: Vec<String>) -> PyResult<()> {
+  let family = font_family(family)?;
+
+  let mut definitions = FONTS.lock().unwrap();
+  let definitions = definitions.get_or_insert_with(FontDefinitions::default);
+
+  if let Some(name) = fonts.iter().find(|name| !definitions.font_data.contains_key(*name)) {
+    return Err(PyValueError::new_err(format!("Unknown font '{}', load it with add_font", name)));
+  }
+  definitions.families.insert(family, fonts);
+
+  FONTS_CHANGED.store(true, Ordering::Relaxed);
+  request_app_repaint();
+  Ok(())
+}
+
+// helpers
+
+fn font_family(name: &str) -> PyResult<FontFamily> {
+  match name {
+    "proportional" => Ok(FontFamily::Proportional),
+    "monospace" => Ok(FontFamily::Monospace),
+    other => Err(PyValueError::new_err(format!("Unknown font family '{}', expected 'proportional' or 'monospace'", other)))
+  }
+}
+
+fn decode_icon(buf: &[u8]) -> PyResult<egui::IconData> {
+  eframe::icon_data::from_png_bytes(buf)
+    .map_err(|e| PyOSError::new_err(format!("Failed to decode png file: {}", e)))
+}
+
+#[derive(Clone, Copy)]
+enum ToastKind {
+  Info,
+  Success,
+  Error,
+}
+
+struct Toast {
+  text: String,
+  kind: ToastKind,
+  duration: f64,
+  // set when the toast is shown for the first time
+  shown_at: Option<f64>,
+}
+
+struct Toasts {
+  queue: Vec<Toast>,
+}
+
+fn request_app_repaint() {
+  if let Some(ctx) = APP_CTX.lock().unwrap().as_ref() {
+    ctx.request_repaint();
+  }
+}
+
+fn push_toast(text: String, kind: ToastKind, duration: f64) -> PyResult<()> {
+  if !(duration.is_finite() && duration >= 0.0) {
+    return Err(PyValueError::new_err("Duration should be a finite, non-negative number of seconds"));
+  }
+
+  TOASTS.lock().unwrap().queue.push(Toast { text, kind, duration, shown_at: None });
+  request_app_repaint();
+  Ok(())
+}
+
+fn show_toasts(ctx: &egui::Context) {
+  let mut toasts = TOASTS.lock().unwrap();
+  let now = ctx.input(|i| i.time);
+
+  toasts.queue.retain_mut(|toast| now - *toast.shown_at.get_or_insert(now) < toast.duration);
+  if toasts.queue.is_empty() {
+    return;
+  }
+
+  egui::Area::new(egui::Id::new("pyegui_toasts"))
+    .anchor(egui::Align2::RIGHT_BOTTOM, [-8.0, -8.0])
+    .order(egui::Order::Foreground)
+    .interactable(false)
+    .show(ctx, |ui| {
+      for toast in &toasts.queue {
+        let color = match toast.kind {
+          ToastKind::Info => ui.visuals().text_color(),
+          ToastKind::Success => egui::Color32::from_rgb(0, 180, 0),
+          ToastKind::Error => ui.visuals().error_fg_color,
+        };
+
+        egui::Frame::popup(ui.style()).show(ui, |ui| {
+          ui.colored_label(color, &toast.text);
+        });
+      }
+    });
+
+  let remaining = toasts.queue
+    .iter()
+    .map(|toast| toast.shown_at.unwrap_or(now) + toast.duration - now)
+    .fold(f64::INFINITY, f64::min);
+  // a duration too long for Duration doesn't need a repaint
+  if let Ok(remaining) = std::time::Duration::try_from_secs_f64(remaining.max(0.0)) {
+    ctx.request_repaint_after(remaining);
+  }
+}
+
+// options shared by all widgets: enabled, visible, size, hover_text, accessibility_label and described_by
+fn add_widget(ui: &mut egui::Ui, widget: impl egui::Widget, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let mut enabled = true;
+  let mut visible = true;
+  let mut size = None;
+  let mut hover_text = None;
+  let mut accessibility_label = None;
+  let mut described_by = None;
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(e) = kwargs.get_item("enabled")? {
+      enabled = e.downcast::<PyBool>()?.extract()?;
+    }
+
+    if let Some(v) = kwargs.get_item("visible")? {
+      visible = v.downcast::<PyBool>()?.extract()?;
+    }
+
+    if let Some(s) = kwargs.get_item("size")? {
+      let (width, height): (f32, f32) = s.extract()?;
+      size = Some(egui::vec2(width, height));
+    }
+
+    if let Some(text) = kwargs.get_item("hover_text")? {
+      hover_text = Some(text.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(text) = kwargs.get_item("accessibility_label")? {
+      accessibility_label = Some(text.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(d) = kwargs.get_item("described_by")? {
+      described_by = Some(extract_description(&d)?);
+    }
+  }
+
+  let add = |ui: &mut egui::Ui| match size {
+    Some(size) => ui.add_sized(size, widget),
+    None => ui.add(widget),
+  };
+
+  let mut r = if enabled && visible {
+    add(ui)
+  } else {
+    ui.scope(|ui| {
+      if !enabled {
+        ui.disable();
+      }
+      if !visible {
+        ui.set_invisible();
+      }
+      add(ui)
+    }).inner
+  };
+
+  if let Some(text) = hover_text {
+    r = r.on_hover_text(text);
+  }
+
+  r.ctx.accesskit_node_builder(r.id, |node| {
+    if let Some(label) = accessibility_label {
+      node.set_label(label);
+    }
+
+    match described_by {
+      Some(Description::Text(text)) => node.set_description(text),
+      Some(Description::Widget(id)) => node.push_described_by(id.value().into()),
+      None => (),
+    }
+  });
+
+  Ok(Response(r))
+}
+
+enum Description {
+  Text(String),
+  Widget(egui::Id),
+}
+
+// described_by is either a text or the Response of the widget that describes this one
+fn extract_description(obj: &Bound<'_, PyAny>) -> PyResult<Description> {
+  if let Ok(response) = obj.downcast::<Response>() {
+    Ok(Description::Widget(response.borrow().0.id))
+  } else if let Ok(text) = obj.downcast::<PyString>() {
+    Ok(Description::Text(text.extract()?))
+  } else {
+    Err(PyTypeError::new_err("described_by must be str or Response"))
+  }
+}
+
+fn frame_options(ui: &egui::Ui, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::Frame> {
+  let mut frame = egui::Frame::new();
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(color) = kwargs.get_item("fill")? {
+      frame = frame.fill(extract_color(&color)?);
+    }
+
+    if let Some(stroke) = kwargs.get_item("stroke")? {
+      let (width, color): (f32, Bound<'_, PyAny>) = stroke.extract()?;
+      frame = frame.stroke(egui::Stroke::new(width, extract_color(&color)?));
+    }
+
+    if let Some(margin) = kwargs.get_item("inner_margin")? {
+      frame = frame.inner_margin(extract_margin(&margin)?);
+    }
+
+    if let Some(margin) = kwargs.get_item("outer_margin")? {
+      frame = frame.outer_margin(extract_margin(&margin)?);
+    }
+
+    if let Some(radius) = kwargs.get_item("corner_radius")? {
+      frame = frame.corner_radius(radius.extract::<f32>()?);
+    }
+
+    if let Some(shadow) = kwargs.get_item("shadow")? {
+      if let Ok(enabled) = shadow.downcast::<PyBool>() {
+        if enabled.is_true() {
+          frame = frame.shadow(ui.visuals().window_shadow);
+        }
+      } else {
+        let (x, y, blur, spread, color): (i8, i8, u8, u8, Bound<'_, PyAny>) = shadow.extract()?;
+        frame = frame.shadow(egui::Shadow { offset: [x, y], blur, spread, color: extract_color(&color)? });
+      }
+    }
+  }
+
+  Ok(frame)
+}
+
+// a margin is either the same on all sides or (left, right, top, bottom)
+fn extract_margin(obj: &Bound<'_, PyAny>) -> PyResult<egui::Margin> {
+  if let Ok((left, right, top, bottom)) = obj.extract::<(f32, f32, f32, f32)>() {
+    return Ok(egui::Margin {
+      left: left as i8,
+      right: right as i8,
+      top: top as i8,
+      bottom: bottom as i8,
+    });
+  }
+
+  Ok(egui::Margin::from(obj.extract::<f32>()?))
+}
+
+fn separator_options(mut separator: egui::Separator, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::Separator> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(spacing) = kwargs.get_item("spacing")? {
+      separator = separator.spacing(spacing.extract()?);
+    }
+
+    if let Some(grow) = kwargs.get_item("grow")? {
+      separator = separator.grow(grow.extract()?);
+    }
+
+    if let Some(shrink) = kwargs.get_item("shrink")? {
+      separator = separator.shrink(shrink.extract()?);
+    }
+  }
+
+  Ok(separator)
+}
+
+fn collapsing_options(heading: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<CollapsingOptions> {
+  let mut options = CollapsingOptions {
+    heading: heading.to_owned(),
+    default_open: false,
+    id_salt: None,
+    open: None,
+  };
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(default_open) = kwargs.get_item("default_open")? {
+      options.default_open = default_open.downcast::<PyBool>()?.extract()?;
+    }
+
+    if let Some(id_salt) = kwargs.get_item("id_salt")? {
+      options.id_salt = Some(id_salt.downcast::<PyString>()?.extract()?);
+    }
+
+    if let Some(open) = kwargs.get_item("open")? {
+      options.open = Some(open.downcast::<Bool>()?.clone().unbind());
+    }
+  }
+
+  Ok(options)
+}
+
+fn combo_box_options(label: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::ComboBox> {
+  let mut combo_box = egui::ComboBox::from_label(label);
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(id_salt) = kwargs.get_item("id_salt")? {
+      combo_box = egui::ComboBox::new(id_salt.downcast::<PyString>()?.to_str()?, label);
+    }
+
+    if let Some(width) = kwargs.get_item("width")? {
+      combo_box = combo_box.width(width.extract()?);
+    }
+
+    if let Some(truncate) = kwargs.get_item("truncate")? {
+      if truncate.downcast::<PyBool>()?.extract()? {
+        combo_box = combo_box.truncate();
+      }
+    }
+
+    if let Some(icon) = kwargs.get_item("icon")? {
+      let icon = icon.downcast::<PyString>()?.extract::<String>()?;
+
+      combo_box = combo_box.icon(move |ui, rect, visuals, _is_open, _above_or_below| {
+        ui.painter().text(
+          rect.center(),
+          egui::Align2::CENTER_CENTER,
+          icon,
+          egui::TextStyle::Button.resolve(ui.style()),
+          visuals.fg_stroke.color
+        );
+      });
+    }
+  }
+
+  Ok(combo_box)
+}
+
+fn image_options<'a>(mut img: egui::Image<'a>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::Image<'a>> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(height) = kwargs.get_item("max_height")? {
+      img = img.max_height(height.downcast::<PyInt>()?.extract()?);
+    }
+
+    if let Some(width) = kwargs.get_item("max_width")? {
+      img = img.max_width(width.downcast::<PyInt>()?.extract()?);
+    }
+
+    if let Some(size) = kwargs.get_item("max_size")? {
+      let (width, height): (f32, f32) = size.extract()?;
+      img = img.max_size(egui::vec2(width, height));
+    }
+
+    if let Some(size) = kwargs.get_item("fit_to_exact_size")? {
+      let (width, height): (f32, f32) = size.extract()?;
+      img = img.fit_to_exact_size(egui::vec2(width, height));
+    }
+
+    if let Some(maintain) = kwargs.get_item("maintain_aspect_ratio")? {
+      img = img.maintain_aspect_ratio(maintain.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(radius) = kwargs.get_item("corner_radius")? {
+      img = img.corner_radius(radius.extract::<f32>()?);
+    }
+
+    if let Some(tint) = kwargs.get_item("tint")? {
+      img = img.tint(extract_color(&tint)?);
+    }
+
+    if let Some(fill) = kwargs.get_item("bg_fill")? {
+      img = img.bg_fill(extract_color(&fill)?);
+    }
+
+    if let Some(sense) = kwargs.get_item("sense")? {
+      img = img.sense(extract_sense(sense.downcast::<PyString>()?.to_str()?)?);
+    }
+  }
+
+  Ok(img)
+}
+
+fn extract_key(key: &Bound<'_, PyAny>) -> PyResult<egui::Key> {
+  if let Ok(key) = key.downcast::<Key>() {
+    return Ok(key.get().0);
+  }
+
+  Key::from_name(key.downcast::<PyString>()?.to_str()?).map(|k| k.0)
+}
+
+fn parse_shortcut(shortcut: &str) -> PyResult<egui::KeyboardShortcut> {
+  let mut parts: Vec<&str> = shortcut.split('+').map(str::trim).collect();
+  let key = parts.pop().filter(|k| !k.is_empty())
+    .ok_or_else(|| PyValueError::new_err(format!("Shortcut '{}' has no key", shortcut)))?;
+
+  let mut modifiers = egui::Modifiers::NONE;
+  for part in parts {
+    modifiers |= match part.to_lowercase().as_str() {
+      "ctrl" => egui::Modifiers::CTRL,
+      "shift" => egui::Modifiers::SHIFT,
+      "alt" => egui::Modifiers::ALT,
+      "maccmd" => egui::Modifiers::MAC_CMD,
+      "cmd" | "command" => egui::Modifiers::COMMAND,
+      other => return Err(PyValueError::new_err(format!("Unknown modifier '{}'", other)))
+    };
+  }
+
+  Ok(egui::KeyboardShortcut::new(modifiers, Key::from_name(key)?.0))
+}
+
+fn font_id(size: f32, monospace: bool) -> egui::FontId {
+  if monospace {
+    egui::FontId::monospace(size)
+  } else {
+    egui::FontId::proportional(size)
+  }
+}
+
+fn extract_align2(anchor: &str) -> PyResult<egui::Align2> {
+  match anchor {
+    "left_top" => Ok(egui::Align2::LEFT_TOP),
+    "left_center" => Ok(egui::Align2::LEFT_CENTER),
+    "left_bottom" => Ok(egui::Align2::LEFT_BOTTOM),
+    "center_top" => Ok(egui::Align2::CENTER_TOP),
+    "center_center" | "center" => Ok(egui::Align2::CENTER_CENTER),
+    "center_bottom" => Ok(egui::Align2::CENTER_BOTTOM),
+    "right_top" => Ok(egui::Align2::RIGHT_TOP),
+    "right_center" => Ok(egui::Align2::RIGHT_CENTER),
+    "right_bottom" => Ok(egui::Align2::RIGHT_BOTTOM),
+    other => Err(PyValueError::new_err(format!("Unknown anchor '{}'", other)))
+  }
+}
+
+// strings and ints give the same Id on every run, other objects use their Python hash
+fn extract_id_salt(value: &Bound<'_, PyAny>) -> PyResult<egui::Id> {
+  if let Ok(s) = value.downcast::<PyString>() {
+    Ok(egui::Id::new(s.to_str()?))
+  } else if let Ok(i) = value.extract::<i64>() {
+    Ok(egui::Id::new(i))
+  } else {
+    Ok(egui::Id::new(value.hash()?))
+  }
+}
+
+fn extract_align(align: &str) -> PyResult<egui::Align> {
+  match align {
+    "min" => Ok(egui::Align::Min),
+    "center" => Ok(egui::Align::Center),
+    "max" => Ok(egui::Align::Max),
+    other => Err(PyValueError::new_err(format!("Unknown align '{}', expected 'min', 'center' or 'max'", other)))
+  }
+}
+
+fn extract_sense(sense: &str) -> PyResult<egui::Sense> {
+  match sense {
+    "hover" => Ok(egui::Sense::hover()),
+    "click" => Ok(egui::Sense::click()),
+    "drag" => Ok(egui::Sense::drag()),
+    "click_and_drag" => Ok(egui::Sense::click_and_drag()),
+    other => Err(PyValueError::new_err(format!("Unknown sense '{}'", other)))
+  }
+}
+
+fn text_options(mut text: egui::RichText, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::RichText> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(color) = kwargs.get_item("color")? {
+      text = text.color(extract_color(&color)?);
+    }
+  }
+
+  Ok(text)
+}
+
+// label options: wrap_mode and max_width
+fn add_label(ui: &mut egui::Ui, text: egui::RichText, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let mut label = egui::Label::new(text);
+  let mut max_width = None;
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(mode) = kwargs.get_item("wrap_mode")? {
+      label = match mode.downcast::<PyString>()?.to_str()? {
+        "wrap" => label.wrap(),
+        "truncate" => label.truncate(),
+        "extend" => label.extend(),
+        other => return Err(PyValueError::new_err(format!("Unknown wrap mode '{}'", other)))
+      };
+    }
+
+    if let Some(width) = kwargs.get_item("max_width")? {
+      max_width = Some(width.extract::<f32>()?);
+    }
+  }
+
+  match max_width {
+    Some(width) => ui.scope(|ui| {
+      ui.set_max_width(width);
+      add_widget(ui, label, kwargs)
+    }).inner,
+    None => add_widget(ui, label, kwargs),
+  }
+}
+
+fn text_edit_options<'a>(mut w: egui::TextEdit<'a>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::TextEdit<'a>> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(hint_text) = kwargs.get_item("hint_text")? {
+      w = w.hint_text(hint_text.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(password) = kwargs.get_item("password")? {
+      w = w.password(password.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(limit) = kwargs.get_item("char_limit")? {
+      w = w.char_limit(limit.downcast::<PyInt>()?.extract()?);
+    }
+
+    if let Some(width) = kwargs.get_item("desired_width")? {
+      w = w.desired_width(width.extract()?);
+    }
+
+    if let Some(rows) = kwargs.get_item("desired_rows")? {
+      w = w.desired_rows(rows.downcast::<PyInt>()?.extract()?);
+    }
+
+    if let Some(interactive) = kwargs.get_item("interactive")? {
+      w = w.interactive(interactive.downcast::<PyBool>()?.extract()?);
+    }
+  }
+
+  Ok(w)
+}
+
+fn add_text_edit(ui: &mut egui::Ui, text: &mut String, multiline: bool, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let layouter_fun = match kwargs {
+    Some(kwargs) => kwargs.get_item("layouter")?,
+    None => None,
+  };
+
+  // errors can't be returned from the layouter, so they are kept until the text field is added
+  let mut layout_err = None;
+  let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+    let blocked = block_ui();
+    let segments = layouter_fun.as_ref()
+      .map(|fun| fun.call1((text,)).and_then(|segments| extract_segments(ui, &segments)))
+      .unwrap_or(Ok(Vec::new()));
+    drop(blocked);
+
+    let job = match segments {
+      Ok(segments) => layout_job(ui, text, segments, wrap_width),
+      Err(err) => {
+        layout_err = Some(err);
+        layout_job(ui, text, Vec::new(), wrap_width)
+      }
+    };
+
+    ui.fonts(|f| f.layout_job(job))
+  };
+
+  let mut w = match multiline {
+    true => egui::TextEdit::multiline(text),
+    false => egui::TextEdit::singleline(text),
+  };
+  w = text_edit_options(w, kwargs)?;
+
+  if layouter_fun.is_some() {
+    w = w.layouter(&mut layouter);
+  }
+
+  let r = add_widget(ui, w, kwargs)?;
+
+  match layout_err {
+    Some(err) => Err(err),
+    None => Ok(r),
+  }
+}
+
+fn extract_segments(ui: &egui::Ui, segments: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize, egui::TextFormat)>> {
+  let mut result = Vec::new();
+
+  for segment in segments.try_iter()? {
+    let (start, end, format): (usize, usize, Bound<'_, PyAny>) = segment?.extract()?;
+    let mut text_format = egui::TextFormat::simple(egui::TextStyle::Body.resolve(ui.style()), ui.visuals().text_color());
+
+    match format.downcast::<PyDict>() {
+      Ok(format) => {
+
+        if let Some(color) = format.get_item("color")? {
+          text_format.color = extract_color(&color)?;
+        }
+
+        if let Some(background) = format.get_item("background")? {
+          text_format.background = extract_color(&background)?;
+        }
+
+        if let Some(italics) = format.get_item("italics")? {
+          text_format.italics = italics.downcast::<PyBool>()?.extract()?;
+        }
+
+        let stroke = egui::Stroke::new(1.0, text_format.color);
+
+        if let Some(underline) = format.get_item("underline")? {
+          if underline.downcast::<PyBool>()?.extract()? {
+            text_format.underline = stroke;
+          }
+        }
+
+        if let Some(strikethrough) = format.get_item("strikethrough")? {
+          if strikethrough.downcast::<PyBool>()?.extract()? {
+            text_format.strikethrough = stroke;
+          }
+        }
+
+        if let Some(monospace) = format.get_item("monospace")? {
+          if monospace.downcast::<PyBool>()?.extract()? {
+            text_format.font_id = egui::TextStyle::Monospace.resolve(ui.style());
+          }
+        }
+      },
+      Err(_) => text_format.color = extract_color(&format)?,
+    }
+
+    result.push((start, end, text_format));
+  }
+
+  Ok(result)
+}
+
+// segments are (start, end, format) in chars. Text outside of them uses the default format
+fn layout_job(ui: &egui::Ui, text: &str, mut segments: Vec<(usize, usize, egui::TextFormat)>, wrap_width: f32) -> egui::text::LayoutJob {
+  let default_format = egui::TextFormat::simple(egui::TextStyle::Body.resolve(ui.style()), ui.visuals().text_color());
+  let byte_offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).chain([text.len()]).collect();
+  let byte = |char_index: usize| byte_offsets[char_index.min(byte_offsets.len() - 1)];
+
+  let mut job = egui::text::LayoutJob::default();
+  let mut cursor = 0;
+
+  segments.sort_by_key(|(start, _, _)| *start);
+  for (start, end, format) in segments {
+    let (start, end) = (byte(start), byte(end));
+    // overlapping segments are skipped
+    if start < cursor || end <= start {
+      continue;
+    }
+
+    job.append(&text[cursor..start], 0.0, default_format.clone());
+    job.append(&text[start..end], 0.0, format);
+    cursor = end;
+  }
+  job.append(&text[cursor..], 0.0, default_format);
+  job.wrap.max_width = wrap_width;
+
+  job
+}
+
+fn button_options<'a>(mut button: egui::Button<'a>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::Button<'a>> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(min_size) = kwargs.get_item("min_size")? {
+      let (width, height): (f32, f32) = min_size.extract()?;
+      button = button.min_size(egui::vec2(width, height));
+    }
+
+    if let Some(fill) = kwargs.get_item("fill")? {
+      button = button.fill(extract_color(&fill)?);
+    }
+
+    if let Some(frame) = kwargs.get_item("frame")? {
+      button = button.frame(frame.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(selected) = kwargs.get_item("selected")? {
+      button = button.selected(selected.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(shortcut) = kwargs.get_item("shortcut")? {
+      let shortcut = parse_shortcut(shortcut.downcast::<PyString>()?.to_str()?)?;
+      button = button.shortcut_text(shortcut.format(&egui::ModifierNames::NAMES, cfg!(target_os = "macos")));
+    }
+  }
+
+  Ok(button)
+}
+
+fn image_button_options<'a>(mut button: egui::ImageButton<'a>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<egui::ImageButton<'a>> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(frame) = kwargs.get_item("frame")? {
+      button = button.frame(frame.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(selected) = kwargs.get_item("selected")? {
+      button = button.selected(selected.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(tint) = kwargs.get_item("tint")? {
+      button = button.tint(extract_color(&tint)?);
+    }
+
+    if let Some(radius) = kwargs.get_item("corner_radius")? {
+      button = button.corner_radius(radius.extract::<f32>()?);
+    }
+  }
+
+  Ok(button)
+}
+
+fn slider_options<'a>(mut slider: egui::Slider<'a>, kwargs: Option<&Bound<'a, PyDict>>) -> PyResult<egui::Slider<'a>> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(prefix) = kwargs.get_item("prefix")? {
+      slider = slider.prefix(prefix.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(suffix) = kwargs.get_item("suffix")? {
+      slider = slider.suffix(suffix.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(step) = kwargs.get_item("step_by")? {
+      slider = slider.step_by(step.extract()?);
+    }
+
+    if let Some(logarithmic) = kwargs.get_item("logarithmic")? {
+      slider = slider.logarithmic(logarithmic.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(show_value) = kwargs.get_item("show_value")? {
+      slider = slider.show_value(show_value.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(clamping) = kwargs.get_item("clamping")? {
+      slider = slider.clamping(match clamping.downcast::<PyString>()?.to_str()? {
+        "never" => egui::SliderClamping::Never,
+        "edits" => egui::SliderClamping::Edits,
+        "always" => egui::SliderClamping::Always,
+        other => return Err(PyValueError::new_err(format!("Unknown clamping '{}'", other)))
+      });
+    }
+
+    if let Some(formatter) = kwargs.get_item("formatter")? {
+      slider = slider.custom_formatter(value_formatter(formatter));
+    }
+
+    if let Some(vertical) = kwargs.get_item("vertical")? {
+      if vertical.downcast::<PyBool>()?.extract()? {
+        slider = slider.vertical();
+      }
+    }
+  }
+
+  Ok(slider)
+}
+
+// formats slider and drag values with a Python function. Falls back to the default format if it raises,
+// the exception is raised by with_formatter_error after the widget is drawn
+fn value_formatter(formatter: Bound<'_, PyAny>) -> impl Fn(f64, std::ops::RangeInclusive<usize>) -> String + '_ {
+  move |value, _| {
+    call_formatter(&formatter, value).unwrap_or_else(|| value.to_string())
+  }
+}
+
+// calls the formatter unless a formatter has already raised while drawing this widget
+fn call_formatter(formatter: &Bound<'_, PyAny>, value: f64) -> Option<String> {
+  if FORMATTER_ERROR.with_borrow(Option::is_some) {
+    return None;
+  }
+
+  let _blocked = block_ui();
+  match formatter.call1((value,)).and_then(|text| text.extract::<String>()) {
+    Ok(text) => Some(text),
+    Err(err) => {
+      FORMATTER_ERROR.set(Some(err));
+      None
+    }
+  }
+}
+
+// raises the exception of a formatter called while the widget was drawn
+fn with_formatter_error<T>(result: PyResult<T>) -> PyResult<T> {
+  match FORMATTER_ERROR.take() {
+    Some(err) => result.and(Err(err)),
+    None => result
+  }
+}
+
+// parses values typed by the user. Exceptions and None mean the text is not a valid value
+fn value_parser(parser: Bound<'_, PyAny>) -> impl Fn(&str) -> Option<f64> + '_ {
+  move |text| {
+    let _blocked = block_ui();
+    parser.call1((text,)).and_then(|value| value.extract()).ok().flatten()
+  }
+}
+
+fn drag_options<'a>(mut drag: egui::DragValue<'a>, kwargs: Option<&Bound<'a, PyDict>>) -> PyResult<egui::DragValue<'a>> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(prefix) = kwargs.get_item("prefix")? {
+      drag = drag.prefix(prefix.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(suffix) = kwargs.get_item("suffix")? {
+      drag = drag.suffix(suffix.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(decimals) = kwargs.get_item("fixed_decimals")? {
+      drag = drag.fixed_decimals(decimals.downcast::<PyInt>()?.extract()?);
+    }
+
+    if let Some(formatter) = kwargs.get_item("formatter")? {
+      drag = drag.custom_formatter(value_formatter(formatter));
+    }
+
+    if let Some(parser) = kwargs.get_item("parser")? {
+      drag = drag.custom_parser(value_parser(parser));
+    }
+  }
+
+  Ok(drag)
+}
+
+fn extract_color(color: &Bound<'_, PyAny>) -> PyResult<egui::Color32> {
+  if let Ok(color) = color.downcast::<Color>() {
+    return Ok(color.borrow().0);
+  }
+
+  if let Ok(hex) = color.downcast::<PyString>() {
+    return Color::from_hex(hex.to_str()?).map(|c| c.0);
+  }
+
+  if let Ok(rgb) = color.downcast::<RGB>() {
+    let rgb = rgb.borrow();
+    return Ok(egui::Rgba::from_rgb(rgb.r, rgb.g, rgb.b).into());
+  }
+
+  if let Ok(rgba) = color.downcast::<RGBA>() {
+    let rgba = rgba.borrow();
+    return Ok(egui::Rgba::from_rgba_unmultiplied(rgba.r, rgba.g, rgba.b, rgba.a).into());
+  }
+
+  if let Ok(hsva) = color.downcast::<HSVA>() {
+    return Ok(hsva.borrow().to_hsva().into());
+  }
+
+  match color.extract::<Vec<u8>>().map_err(|_| PyTypeError::new_err(COLOR_ERR))?.as_slice() {
+    [r, g, b] => Ok(egui::Color32::from_rgb(*r, *g, *b)),
+    [r, g, b, a] => Ok(egui::Color32::from_rgba_unmultiplied(*r, *g, *b, *a)),
+    _ => Err(PyValueError::new_err(COLOR_ERR))
+  }
+}
+
+fn show_seconds(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<bool> {
+  match kwargs.map(|kwargs| kwargs.get_item("show_seconds")).transpose()?.flatten() {
+    Some(seconds) => seconds.downcast::<PyBool>()?.extract(),
+    None => Ok(false),
+  }
+}
+
+fn time_fields(ui: &mut egui::Ui, time: &mut NaiveTime, seconds: bool) -> egui::Response {
+  let (mut h, mut m, mut s) = (time.hour(), time.minute(), time.second());
+  let two_digits = |n: f64, _| format!("{:02}", n as u32);
+
+  let r = ui.horizontal(|ui| {
+    ui.spacing_mut().item_spacing.x = 2.0;
+
+    let mut r = ui.add(egui::DragValue::new(&mut h).range(0..=23).custom_formatter(two_digits));
+    ui.label(":");
+    r |= ui.add(egui::DragValue::new(&mut m).range(0..=59).custom_formatter(two_digits));
+    if seconds {
+      ui.label(":");
+      r |= ui.add(egui::DragValue::new(&mut s).range(0..=59).custom_formatter(two_digits));
+    }
+    r
+  }).inner;
+
+  // untouched times keep their fractional seconds
+  if r.changed() {
+    if let Some(new_time) = NaiveTime::from_hms_opt(h, m, s) {
+      *time = new_time;
+    }
+  }
+  r
+}
+
+fn color_alpha_mode(mode: &str) -> PyResult<egui::color_picker::Alpha> {
+  match mode {
+    "blend" => Ok(egui::color_picker::Alpha::OnlyBlend),
+    "blend_or_additive" => Ok(egui::color_picker::Alpha::BlendOrAdditive),
+    "opaque" => Ok(egui::color_picker::Alpha::Opaque),
+    other => Err(PyValueError::new_err(format!("Unknown alpha mode '{}'", other)))
+  }
+}
+
+fn marker_shape(shape: &str) -> PyResult<egui_plot::MarkerShape> {
+  match shape {
+    "circle" => Ok(egui_plot::MarkerShape::Circle),
+    "diamond" => Ok(egui_plot::MarkerShape::Diamond),
+    "square" => Ok(egui_plot::MarkerShape::Square),
+    "cross" => Ok(egui_plot::MarkerShape::Cross),
+    "plus" => Ok(egui_plot::MarkerShape::Plus),
+    "up" => Ok(egui_plot::MarkerShape::Up),
+    "down" => Ok(egui_plot::MarkerShape::Down),
+    "left" => Ok(egui_plot::MarkerShape::Left),
+    "right" => Ok(egui_plot::MarkerShape::Right),
+    "asterisk" => Ok(egui_plot::MarkerShape::Asterisk),
+    _ => Err(PyValueError::new_err(format!("Unknown marker shape '{}'", shape)))
+  }
+}
+
+// Arrays supporting the buffer protocol (e.g. numpy) are copied in one go instead of element by element.
+// Integer arrays are converted to floats
+fn extract_buffer(obj: &Bound<'_, PyAny>) -> PyResult<Option<(Vec<usize>, Vec<f64>)>> {
+  if let Ok(buf) = PyBuffer::<f64>::get(obj) {
+    return Ok(Some((buf.shape().to_vec(), buf.to_vec(obj.py())?)));
+  }
+
+  let converted = convert_buffer::<f32>(obj, f64::from)
+    .or_else(|| convert_buffer::<i64>(obj, |v| v as f64))
+    .or_else(|| convert_buffer::<i32>(obj, f64::from))
+    .or_else(|| convert_buffer::<i16>(obj, f64::from))
+    .or_else(|| convert_buffer::<i8>(obj, f64::from))
+    .or_else(|| convert_buffer::<u64>(obj, |v| v as f64))
+    .or_else(|| convert_buffer::<u32>(obj, f64::from))
+    .or_else(|| convert_buffer::<u16>(obj, f64::from))
+    .or_else(|| convert_buffer::<u8>(obj, f64::from));
+  if let Some(converted) = converted {
+    return converted.map(Some);
+  }
+
+  // e.g. a complex or a string numpy array, that would fail confusingly element by element
+  if let Ok(dtype) = obj.getattr("dtype") {
+    return Err(PyTypeError::new_err(format!("Expected a float or integer array, got {} dtype", dtype.str()?)));
+  }
+
+  Ok(None)
+}
+
+fn convert_buffer<T: pyo3::buffer::Element + Copy>(obj: &Bound<'_, PyAny>, convert: fn(T) -> f64) -> Option<PyResult<(Vec<usize>, Vec<f64>)>> {
+  let buf = PyBuffer::<T>::get(obj).ok()?;
+
+  Some(buf.to_vec(obj.py()).map(|values| (buf.shape().to_vec(), values.into_iter().map(convert).collect())))
+}
+
+// accepts uint8 arrays with (H, W, 3) or (H, W, 4) shape and PIL images
+fn extract_color_image(obj: &Bound<'_, PyAny>) -> PyResult<egui::ColorImage> {
+  if let Ok(buf) = PyBuffer::<u8>::get(obj) {
+    let pixels = buf.to_vec(obj.py())?;
+
+    return match *buf.shape() {
+      [h, w, 3] => Ok(egui::ColorImage::from_rgb([w, h], &pixels)),
+      [h, w, 4] => Ok(egui::ColorImage::from_rgba_unmultiplied([w, h], &pixels)),
+      _ => Err(PyValueError::new_err(IMAGE_SHAPE_ERR))
+    };
+  }
+
+  if obj.hasattr("tobytes")? && obj.hasattr("mode")? {
+    let rgba = obj.call_method1("convert", ("RGBA",))?;
+    let (w, h): (usize, usize) = rgba.getattr("size")?.extract()?;
+    let pixels = rgba.call_method0("tobytes")?;
+
+    return Ok(egui::ColorImage::from_rgba_unmultiplied([w, h], pixels.downcast::<PyBytes>()?.as_bytes()));
+  }
+
+  Err(PyTypeError::new_err(IMAGE_SHAPE_ERR))
+}
+
+fn texture_options(filter: &str) -> PyResult<egui::TextureOptions> {
+  match filter {
+    "linear" => Ok(egui::TextureOptions::LINEAR),
+    "nearest" => Ok(egui::TextureOptions::NEAREST),
+    other => Err(PyValueError::new_err(format!("Unknown texture filter '{}'", other)))
+  }
+}
+
+fn extract_plot_points(points: &Bound<'_, PyAny>) -> PyResult<Vec<egui_plot::PlotPoint>> {
+  match extract_buffer(points)? {
+    Some((shape, values)) => {
+      if shape.len() != 2 || shape[1] != 2 {
+        return Err(PyValueError::new_err(POINTS_SHAPE_ERR));
+      }
+      Ok(values.chunks_exact(2).map(|p| egui_plot::PlotPoint::new(p[0], p[1])).collect())
+    },
+    None => Ok(points.extract::<Vec<(f64, f64)>>()?
+      .into_iter()
+      .map(|(x, y)| egui_plot::PlotPoint::new(x, y))
+      .collect())
+  }
+}
+
+fn extract_path_points(points: &Bound<'_, PyAny>) -> PyResult<Vec<egui::Pos2>> {
+  match extract_buffer(points)? {
+    Some((shape, values)) => {
+      if shape.len() != 2 || shape[1] != 2 {
+        return Err(PyValueError::new_err(POINTS_SHAPE_ERR));
+      }
+      Ok(values.chunks_exact(2).map(|p| egui::pos2(p[0] as f32, p[1] as f32)).collect())
+    },
+    None => Ok(points.extract::<Vec<(f32, f32)>>()?
+      .into_iter()
+      .map(|(x, y)| egui::pos2(x, y))
+      .collect())
+  }
+}
+
+fn extract_fill(fill: Option<&Bound<'_, PyAny>>) -> PyResult<egui::Color32> {
+  match fill {
+    Some(fill) if !fill.is_none() => extract_color(fill),
+    _ => Ok(egui::Color32::TRANSPARENT)
+  }
+}
+
+fn extract_values(values: &Bound<'_, PyAny>) -> PyResult<Vec<f64>> {
+  match extract_buffer(values)? {
+    Some((shape, values)) => {
+      if shape.len() != 1 {
+        return Err(PyValueError::new_err(VALUES_SHAPE_ERR));
+      }
+      Ok(values)
+    },
+    None => values.extract()
+  }
+}
+
+// closes the menu if a button (but not a submenu button, a checkbox or a text field) was clicked in it.
+// first_event is the number of output events before the content of the menu was drawn
+fn close_menu_on_leaf_click(ui: &mut egui::Ui, first_event: usize, submenu_clicked: bool) {
+  if submenu_clicked {
+    return;
+  }
+
+  let button_clicked = ui.ctx().output(|o| o.events[first_event..].iter().any(|event| matches!(
+    event,
+    egui::output::OutputEvent::Clicked(info)
+      if matches!(info.typ, egui::WidgetType::Button | egui::WidgetType::ImageButton | egui::WidgetType::Link)
+  )));
+
+  if button_clicked {
+    ui.close_menu();
+  }
+}
+
+fn legend_corner(corner: &str) -> PyResult<egui_plot::Corner> {
+  match corner {
+    "top_left" => Ok(egui_plot::Corner::LeftTop),
+    "top_right" => Ok(egui_plot::Corner::RightTop),
+    "bottom_left" => Ok(egui_plot::Corner::LeftBottom),
+    "bottom_right" => Ok(egui_plot::Corner::RightBottom),
+    _ => Err(PyValueError::new_err(format!("Unknown legend position '{}'", corner)))
+  }
+}
+
+// the exception is raised by with_formatter_error after the plot is drawn
+fn axis_formatter<'py>(formatter: Bound<'py, PyAny>) -> impl Fn(egui_plot::GridMark, &std::ops::RangeInclusive<f64>) -> String + 'py {
+  move |mark, _range| call_formatter(&formatter, mark.value).unwrap_or_default()
+}
+
+fn extract_plot_item<'py>(item: &Bound<'py, PyAny>) -> PyResult<PlotItem<'py>> {
+  if let Ok(line) = item.downcast::<Line>() {
+    return Ok(PlotItem::Line(line.borrow()));
+  }
+  if let Ok(points) = item.downcast::<Points>() {
+    return Ok(PlotItem::Points(points.borrow()));
+  }
+  if let Ok(chart) = item.downcast::<BarChart>() {
+    return Ok(PlotItem::BarChart(chart.borrow()));
+  }
+
+  Err(PyTypeError::new_err(PLOT_ITEM_ERR))
+}
+
+// removes the pushed Ui, and everything pushed after it, from the stack when dropped.
+// Nothing is removed if the Ui is not on the stack anymore, e.g. a Container entered in a previous frame
+struct UiGuard {
+  index: usize,
+  ui: *mut egui::Ui,
+}
+
+impl Drop for UiGuard {
+  fn drop(&mut self) {
+    UI_STACK.with_borrow_mut(|stack| {
+      if stack.get(self.index) == Some(&self.ui) {
+        stack.truncate(self.index);
+      }
+    });
+  }
+}
+
+fn push_ui(ui: &mut egui::Ui) -> UiGuard {
+  UI_STACK.with_borrow_mut(|stack| {
+    stack.push(ui);
+    UiGuard { index: stack.len() - 1, ui }
+  })
+}
+
+// makes UI functions raise until the guard is dropped. Used around Python callbacks that egui calls
+// while it uses the Ui, e.g. formatters, so that they can't get a second reference to it
+fn block_ui() -> UiGuard {
+  UI_STACK.with_borrow_mut(|stack| {
+    stack.push(std::ptr::null_mut());
+    UiGuard { index: stack.len() - 1, ui: std::ptr::null_mut() }
+  })
+}
+
+// the Ui must not be used after the function that pushed it returns
+unsafe fn current_ui<'a>() -> PyResult<&'a mut egui::Ui> {
+  match UI_STACK.with_borrow(|stack| stack.last().copied()) {
+    None => Err(PyRuntimeError::new_err(UI_CALL_OUTSIDE_UPDATE_FUNC)),
+    Some(ui) if ui.is_null() => Err(PyRuntimeError::new_err(UI_CALL_IN_CALLBACK)),
+    Some(ui) => Ok(&mut *ui),
+  }
+}
+
+unsafe fn run_nested_update_func(ui: &mut egui::Ui, update_fun: Bound<'_, PyAny>) -> PyResult<()> {
+  run_nested_update_func_with_args(ui, &update_fun, ())
+}
+
+unsafe fn run_nested_update_func_with_args<'py>(
+  ui: &mut egui::Ui,
+  update_fun: &Bound<'py, PyAny>,
+  args: impl PyCallArgs<'py>
+) -> PyResult<()> {
+  let guard = push_ui(ui);
+  let result = update_fun.call1(args);
+  drop(guard);
+
+  let has_handler = ERROR_HANDLER.lock().unwrap().is_some();
+
+  match result {
+    // the error handler sees every exception, so it must reach update_func
+    Err(err) if has_handler => Err(err),
+    // let the exception reach update_func
+    Err(err) => match *ERROR_POLICY.lock().unwrap() {
+      ErrorPolicy::Raise => Err(err),
+      ErrorPolicy::Log => {
+        log_error(update_fun.py(), &err);
+        Ok(())
+      },
+      ErrorPolicy::Ignore => Ok(())
+    },
+    Ok(_) => Ok(())
+  }
+} 
+
+// UI functions
+
+/// Show large text
+///
+/// Example::
+///
+///     heading("hello") 
+///     heading("error", color=(255, 0, 0))
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn heading(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text).heading(), kwargs)?, kwargs)
+}
+
+/// Show monospace (fixed width) text.
+///
+/// Example::
+///
+///     monospace("hello") 
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn monospace(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text).monospace(), kwargs)?, kwargs)
+}
+
+/// Show small text.
+///
+/// Example::
+///
+///     small("hello") 
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn small(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text).small(), kwargs)?, kwargs)
+}
+
+/// Show text that stand out a bit (e.g. slightly brighter).
+///
+/// Example::
+///
+///     strong("hello") 
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn strong(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text).strong(), kwargs)?, kwargs)
+}
+
+/// Show text that is weaker (fainter color).
+///
+/// Example::
+///
+///     weak("hello") 
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn weak(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text).weak(), kwargs)?, kwargs)
+}
+
+/// Show some text.
+///
+/// All text functions (heading, label, monospace, small, strong, weak, code and colored_label) accept these arguments:
+///
+/// Args:
+///     color (Color | str | tuple[int, int, int]): color of the text
+///
+///     wrap_mode (str): "wrap" wraps long text, "truncate" cuts it with "…" and "extend" never wraps it
+///
+///     max_width (float): text wider than this is wrapped or truncated
+///
+/// Example::
+/// 
+///     label("some text") 
+///     label("saved", color=(0, 160, 0))
+///     label(long_path, wrap_mode="truncate", max_width=200)
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn label(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text), kwargs)?, kwargs)
+}
+
+/// Show text in the given color.
+///
+/// Example::
+///
+///     if connected.value:
+///       colored_label((0, 160, 0), "online")
+///     else:
+///       colored_label(RGB(1.0, 0.0, 0.0), "offline")
+#[pyfunction]
+#[pyo3(signature = (color, text, **kwargs))]
+unsafe fn colored_label(color: &Bound<'_, PyAny>, text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, egui::RichText::new(text).color(extract_color(color)?), kwargs)
+}
+
+/// Show text as monospace with a gray background.
+///
+/// Example::
+///
+///     code("print(42 + 27)") 
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn code(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_label(ui, text_options(egui::RichText::new(text).code(), kwargs)?, kwargs)
+}
+
+/// Show singleline text field and update the text
+///
+/// Example::
+///
+///     text = Str("print(42 + 27)")
+///     # inside update func
+///     code_editor(text)
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn code_editor(text: &mut Str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_widget(ui, egui::TextEdit::multiline(&mut text.value).code_editor(), kwargs)
+}
+
+/// Show singleline text field and update the text
+///
+/// Args:
+///     hint_text (str): text shown when the field is empty
+///
+///     password (bool): hide the text with dots
+///
+///     char_limit (int): max amount of characters
+///
+///     desired_width (float): width of the field
+///
+///     interactive (bool): when False the text can be selected and copied, but not edited. Default is True
+///
+///     layouter (Callable[[str], list[tuple[int, int, dict | tuple[int, int, int]]]]): your function that receives the text and returns (start, end, format) segments. format is a color or a dict with color, background, italics, underline, strikethrough and monospace keys
+///
+/// Example::
+///
+///     text = Str("editable")
+///     # inside update func
+///     text_edit_singleline(text, hint_text="hint me bro")
+///     text_edit_singleline(password, password=True, char_limit=32)
+///
+///     def highlight_numbers(text):
+///       return [(m.start(), m.end(), (255, 120, 0)) for m in re.finditer(r"\d+", text)]
+///
+///     text_edit_singleline(text, layouter=highlight_numbers)
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn text_edit_singleline(
+  text: &mut Str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_text_edit(ui, &mut text.value, false, kwargs)
+}
+
+/// Show multiline text field and update the text. Accepts the same arguments as text_edit_singleline and:
+///
+/// Args:
+///     desired_rows (int): height of the field in rows
+/// 
+/// Example::
+///
+///     text = Str("editable")
+///     # inside update func
+///     text_edit_multiline(text, hint_text="hint")
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn text_edit_multiline(
+  text: &mut Str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_text_edit(ui, &mut text.value, true, kwargs)
+}
+
+/// Returns true if the button was clicked this frame
+/// 
+/// if button_clicked("click me"):
+///   print("click me, my friend")
+#[pyfunction]
+unsafe fn button_clicked(text: &str) -> PyResult<bool> {
+  let ui = current_ui()?;
+
+  Ok(ui.button(text).clicked())
+}
+
+/// Returns true if the small button was clicked this frame
+/// 
+/// if small_button_clicked("click me"):
+///   print("click me, my friend")
+#[pyfunction]
+unsafe fn small_button_clicked(text: &str) -> PyResult<bool> {
+  let ui = current_ui()?;
+
+  Ok(ui.small_button(text).clicked())
+}
+
+/// Show a button and return its Response
+///
+/// Args:
+///     text (str): text of the button
+///
+///     min_size (tuple[float, float]): minimal width and height of the button
+///
+///     fill (Color | str | tuple[int, int, int]): background color of the button
+///
+///     frame (bool): whether to draw the button frame. Default is True
+///
+///     selected (bool): whether the button looks selected
+///
+///     shortcut (str): shortcut shown on the right side of the button, e.g. "Ctrl+S". Check it with Context.shortcut_pressed
+///
+/// Example::
+///
+///     r = button("click me")
+///     if r.double_clicked():
+///       print("double clicked")
+///
+///     button("ok", enabled=False, min_size=(80, 24), fill=(0, 120, 0))
+///
+///     if button("Save", shortcut="Cmd+S").clicked() or ctx.shortcut_pressed("Cmd+S"):
+///       save()
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn button(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_widget(ui, button_options(egui::Button::new(text), kwargs)?, kwargs)
+}
+
+/// Show a small button and return its Response
+///
+/// Example::
+///
+///     if small_button("click me").hovered():
+///       label("hovered")
+#[pyfunction]
+#[pyo3(signature = (text, **kwargs))]
+unsafe fn small_button(text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_widget(ui, button_options(egui::Button::new(text).small(), kwargs)?, kwargs)
+}
+
+/// Start a ui with horizontal layout. After you have called this, the function registers the contents as any other widget.
+/// 
+/// Elements will be centered on the Y axis, i.e. adjusted up and down to lie in the center of the horizontal layout. The initial height is style.spacing.interact_size.y. Centering is almost always what you want if you are planning to mix widgets or use different types of text.
+/// 
+/// If you don’t want the contents to be centered, use horizontal_top instead.
+/// 
+/// Example::
+///
+///     def horizontal_update_func():
+///       heading("I'm horizontal")
+///     
+///     horizontal(horizontal_update_func)
+///
+///     # or without a callback
+///     with horizontal():
+///       heading("I'm horizontal")
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn horizontal(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.horizontal(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(horizontal_layout(ui, egui::Align::Center), Some(ui.spacing().interact_size.y)))))
+  }
+}
+
+/// Like horizontal, but allocates the full vertical height and then centers elements vertically.
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn horizontal_centered(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.horizontal_centered(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(horizontal_layout(ui, egui::Align::Center), None))))
+  }
+}
+/// Like horizontal, but aligns content with top.
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn horizontal_top(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.horizontal_top(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(horizontal_layout(ui, egui::Align::Min), Some(ui.spacing().interact_size.y)))))
+  }
+}
+
+/// Start a ui with horizontal layout that wraps to a new row when it reaches the right edge of the max_size. After you have called this, the function registers the contents as any other widget.
+/// 
+/// Elements will be centered on the Y axis, i.e. adjusted up and down to lie in the center of the horizontal layout. The initial height is style.spacing.interact_size.y. Centering is almost always what you want if you are planning to mix widgets or use different types of text.
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn horizontal_wrapped(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.horizontal_wrapped(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(horizontal_layout(ui, egui::Align::Center).with_main_wrap(true), Some(ui.spacing().interact_size.y)))))
+  }
+}
+
+/// Start a vertical layout where elements are centered horizontally.
+///
+/// Example::
+///
+///     with vertical_centered():
+///       heading("Sign in")
+///       text_edit_singleline(user, hint_text="User")
+///       text_edit_singleline(password, password=True, hint_text="Password")
+///       button("Sign in")
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn vertical_centered(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.vertical_centered(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(egui::Layout::top_down(egui::Align::Center), None))))
+  }
+}
+
+/// Like vertical_centered, but elements are stretched to the full width, e.g. buttons.
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn vertical_centered_justified(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.vertical_centered_justified(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(egui::Layout::top_down(egui::Align::Center).with_cross_justify(true), None))))
+  }
+}
+
+/// Put a single widget in the center of the remaining space and stretch it to fill that space.
+///
+/// Example::
+///
+///     if not items:
+///       centered_and_justified(lambda: label("Nothing here yet"))
+///
+///     with centered_and_justified():
+///       if button_clicked("Start"):
+///         start_game()
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn centered_and_justified(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+  let layout = egui::Layout::centered_and_justified(egui::Direction::TopDown);
+
+  match update_fun {
+    Some(update_fun) => ui.with_layout(layout, |ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(layout, None))))
+  }
+}
+
+
+/// Place the contents with the given Layout, in all the available space.
+///
+/// Example::
+///
+///     # a row of buttons aligned to the right
+///     with with_layout(Layout.right_to_left()):
+///       if button_clicked("Cancel"):
+///         close()
+///       if button_clicked("OK"):
+///         save()
+///
+///     # a status line at the bottom
+///     with_layout(Layout.bottom_up(), lambda: label("Ready"))
+#[pyfunction]
+#[pyo3(signature = (layout, update_fun = None))]
+unsafe fn with_layout(layout: &Layout, update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.with_layout(layout.0, |ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Layout(layout.0, None))))
+  }
+}
+
+/// A CollapsingHeader that starts out collapsed.
+///
+/// Args:
+///     heading (str): text of the header
+///
+///     update_fun (Callable[[], None] | None): your function that draws the body
+///
+///     default_open (bool): whether the header starts out open. Default is False
+///
+///     id_salt (str): use it if you have several headers with the same text
+///
+///     open (Bool): holds whether the header is open. Change its value to open or close the header
+///
+/// Example::
+///
+///     def update_func():
+///       heading("hi")
+///     collapsing("collapsed", update_func)
+///
+///     # or without a callback. open tells whether the body is shown
+///     with collapsing("collapsed") as open:
+///       heading("hi")
+///
+///     # "expand all" button
+///     sections = {name: Bool(False) for name in ["General", "Advanced"]}
+///     if button_clicked("Expand all"):
+///       for open in sections.values():
+///         open.value = True
+///     for name, open in sections.items():
+///       collapsing(name, lambda: label(name), open=open)
+#[pyfunction]
+#[pyo3(signature = (heading, update_fun = None, **kwargs))]
+unsafe fn collapsing(heading: &str, update_fun: Option<Bound<'_, PyAny>>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+  let options = collapsing_options(heading, kwargs)?;
+
+  match update_fun {
+    Some(update_fun) => {
+      let py = update_fun.py();
+      let mut header = egui::CollapsingHeader::new(heading).default_open(options.default_open);
+
+      if let Some(id_salt) = &options.id_salt {
+        header = header.id_salt(id_salt);
+      }
+      if let Some(open) = &options.open {
+        header = header.open(Some(open.borrow(py).value));
+      }
+
+      let r = header.show(ui, |ui| run_nested_update_func(ui, update_fun));
+
+      // with open set egui ignores clicks, so the click toggles the Bool and the header follows it next frame
+      if let Some(open) = &options.open {
+        if r.header_response.clicked() {
+          let mut open = open.borrow_mut(py);
+          open.value = !open.value;
+          ui.ctx().request_repaint();
+        }
+      }
+
+      r.body_returned.unwrap_or(Ok(()))?;
+      Ok(None)
+    },
+    None => Ok(Some(Container::new(ContainerKind::Collapsing(options))))
+  }
+}
+
+/// Create a child ui which is indented to the right.
+/// Example::
+///
+///     def update_func():
+///       heading("I'm indented")
+///     indent(update_func)
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn indent(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.indent("your mom", |ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Indent)))
+  }
+}
+
+/// Visually groups the contents together.
+///
+/// Example::
+///
+///     def update_func():
+///       heading("hi")
+///       heading("there")
+///     
+///     group(update_func)
+///
+///     # or without a callback
+///     with group():
+///       heading("hi")
+///       heading("there")
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn group(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.group(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Group)))
+  }
+}
+
+/// A box around the contents with your background, outline, margins and shadow. Use it for cards and highlighted boxes.
+///
+/// Without arguments it draws nothing, so set at least fill or stroke.
+///
+/// Args:
+///     update_fun (Callable[[], None] | None): your function that draws the contents. Without it frame can be used in a with statement
+///
+///     fill (Color): background color
+///
+///     stroke (tuple[float, Color]): width and color of the outline
+///
+///     inner_margin (float | tuple[float, float, float, float]): space between the outline and the contents, either the same on all sides or (left, right, top, bottom)
+///
+///     outer_margin (float | tuple[float, float, float, float]): space around the outline
+///
+///     corner_radius (float): corner radius
+///
+///     shadow (bool | tuple[int, int, int, int, Color]): True for the shadow of windows, or (offset_x, offset_y, blur, spread, color)
+///
+/// Example::
+///
+///     with frame(fill="#2a2a3a", stroke=(1, Color.GRAY), inner_margin=12, corner_radius=8, shadow=True):
+///       heading("Revenue")
+///       label("$12,400")
+///
+///     frame(lambda: label("Saved"), fill=Color.DARK_GREEN, inner_margin=(8, 8, 4, 4))
+#[pyfunction]
+#[pyo3(signature = (update_fun = None, **kwargs))]
+unsafe fn frame(update_fun: Option<Bound<'_, PyAny>>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+  let frame = frame_options(ui, kwargs)?;
+
+  match update_fun {
+    Some(update_fun) => frame.show(ui, |ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Frame(frame))))
+  }
+}
+
+/// Give the widgets inside their own Id scope. Use it when widgets with the same label are created in a loop,
+/// otherwise they share their state, e.g. whether a collapsing header is open.
+///
+/// Args:
+///     value (str | int | Hashable): something unique for each call, e.g. the index or the id of an item
+///
+///     update_fun (Callable[[], None] | None): your function that draws the widgets. Without it push_id can be used in a with statement
+///
+/// Example::
+///
+///     for i, item in enumerate(items):
+///       with push_id(i):
+///         with collapsing("Details") as open:
+///           if open:
+///             label(item.details)
+#[pyfunction]
+#[pyo3(signature = (value, update_fun = None))]
+unsafe fn push_id(value: &Bound<'_, PyAny>, update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+  let id = extract_id_salt(value)?;
+
+  match update_fun {
+    Some(update_fun) => ui.push_id(id, |ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::PushId(id))))
+  }
+}
+
+/// Give the contents exactly this size. A single widget inside is stretched to fill it.
+///
+/// Every widget function accepts the size argument that does the same for one widget, use add_sized
+/// to give a fixed box to containers or several widgets.
+///
+/// Args:
+///     size (tuple[float, float]): width and height
+///
+///     update_fun (Callable[[], None] | None): your function that draws the contents. Without it add_sized can be used in a with statement
+///
+/// Example::
+///
+///     # every card has the same size, whatever its text
+///     with horizontal():
+///       for card in cards:
+///         with add_sized((150, 80)):
+///           with group():
+///             label(card.title)
+#[pyfunction]
+#[pyo3(signature = (size, update_fun = None))]
+unsafe fn add_sized(size: (f32, f32), update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+  let size = egui::vec2(size.0, size.1);
+
+  match update_fun {
+    Some(update_fun) => run_nested_update_func(&mut sized_child(ui, size), update_fun).map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Sized(size))))
+  }
+}
+
+/// Create a scoped child ui.
+/// 
+/// You can use this to temporarily change the Style of a sub-region.
+///
+/// Example::
+///
+///     def update_func():
+///       heading("0.5 opacity")
+///       set_opacity(0.5)
+///     
+///     heading("normal opacity")
+///     scope(update_func)
+#[pyfunction]
+#[pyo3(signature = (update_fun = None))]
+unsafe fn scope(update_fun: Option<Bound<'_, PyAny>>) -> PyResult<Option<Container>> {
+  let ui = current_ui()?;
+
+  match update_fun {
+    Some(update_fun) => ui.scope(|ui| run_nested_update_func(ui, update_fun)).inner.map(|_| None),
+    None => Ok(Some(Container::new(ContainerKind::Scope)))
+  }
+}
+
+/// Add vertical and/or horizontal scrolling to a contained Ui. Returns the current scroll offset as (x, y).
+///
+/// Args:
+///     update_fun (Callable[[], None]): your function that draws the content
+///
+///     hscroll (bool): enable horizontal scrolling. Default is False
+///
+///     vscroll (bool): enable vertical scrolling. Default is True
+///
+///     max_height (float): the area will be at most this tall
+///
+///     max_width (float): the area will be at most this wide
+///
+///     auto_shrink (bool): whether the area shrinks to fit its content. Default is True
+///
+///     stick_to_bottom (bool): keep the view scrolled to the bottom when new content is added, e.g. for chats and logs
+///
+///     stick_to_right (bool): keep the view scrolled to the right when new content is added
+///
+///     offset (tuple[float, float]): scroll to this (x, y) offset
+///
+///     id_salt (str): use it if you have several scroll areas with the same content
+///
+/// Example::
+///
+///     def messages():
+///       for m in chat:
+///         label(m)
+///
+///     scroll_area(messages, max_height=200, stick_to_bottom=True)
+#[pyfunction]
+#[pyo3(signature = (update_fun, **kwargs))]
+unsafe fn scroll_area(
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<(f32, f32)> {
+  let mut area = egui::ScrollArea::vertical();
 
-        Python::with_gil(|py| {
-          if let Err(err) = self.update_func.call1((ctx_r,)) {
-            err.display(py);
-          }
+  if let Some(kwargs) = kwargs {
+
+    if let Some(hscroll) = kwargs.get_item("hscroll")? {
+      area = area.hscroll(hscroll.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(vscroll) = kwargs.get_item("vscroll")? {
+      area = area.vscroll(vscroll.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(height) = kwargs.get_item("max_height")? {
+      area = area.max_height(height.extract()?);
+    }
+
+    if let Some(width) = kwargs.get_item("max_width")? {
+      area = area.max_width(width.extract()?);
+    }
+
+    if let Some(auto_shrink) = kwargs.get_item("auto_shrink")? {
+      area = area.auto_shrink(auto_shrink.downcast::<PyBool>()?.extract::<bool>()?);
+    }
+
+    if let Some(stick) = kwargs.get_item("stick_to_bottom")? {
+      area = area.stick_to_bottom(stick.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(stick) = kwargs.get_item("stick_to_right")? {
+      area = area.stick_to_right(stick.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(offset) = kwargs.get_item("offset")? {
+      let (x, y): (f32, f32) = offset.extract()?;
+      area = area.scroll_offset(egui::vec2(x, y));
+    }
+
+    if let Some(id_salt) = kwargs.get_item("id_salt")? {
+      area = area.id_salt(id_salt.downcast::<PyString>()?.extract::<String>()?);
+    }
+  }
+
+  let output = area.show(current_ui()?, |ui| run_nested_update_func(ui, update_fun));
+
+  output.inner.map(|_| (output.state.offset.x, output.state.offset.y))
+}
+
+/// A pan and zoom canvas. Drag to pan, scroll with Ctrl or pinch to zoom.
+///
+/// view is the part of the canvas that is visible, in canvas coordinates. It is updated in place when the user pans or zooms,
+/// and you can pass another Rect to move the view. An empty Rect makes the view fit the content.
+///
+/// Args:
+///     view (Rect): visible part of the canvas, keep it between frames
+///
+///     update_fun (Callable[[], None]): your function that draws the content
+///
+///     zoom_range (tuple[float, float]): min and max zoom, both positive. Default is (1.2e-7, 1.0), so the content is never magnified
+///
+///     max_inner_size (tuple[float, float]): max size of the content
+///
+/// Example::
+///
+///     view = Rect((0, 0), (0, 0))
+///
+///     def nodes():
+///         for n in graph:
+///             painter().circle_filled(n.pos, 10, "#4caf50")
+///
+///     def update_func(ctx):
+///         global view
+///         scene(view, nodes, zoom_range=(0.1, 4.0))
+///         label(f"showing {view.min} - {view.max}")
+///         if button_clicked("Reset view"):
+///             view = Rect((0, 0), (0, 0))
+#[pyfunction]
+#[pyo3(signature = (view, update_fun, **kwargs))]
+unsafe fn scene(
+  view: &Bound<'_, Rect>,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let mut s = egui::Scene::new();
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(zoom_range) = kwargs.get_item("zoom_range")? {
+      let (min, max): (f32, f32) = zoom_range.extract()?;
+      // egui panics on such ranges
+      if !(min.is_finite() && max.is_finite() && 0.0 < min && min <= max) {
+        return Err(PyValueError::new_err("zoom_range should be (min, max) with 0 < min <= max"));
+      }
+      s = s.zoom_range(min..=max);
+    }
+
+    if let Some(size) = kwargs.get_item("max_inner_size")? {
+      let (width, height): (f32, f32) = size.extract()?;
+      s = s.max_inner_size(egui::vec2(width, height));
+    }
+  }
+
+  // view is not borrowed while update_fun runs, so update_fun can read it
+  let mut rect = view.borrow().0;
+  let r = s.show(current_ui()?, &mut rect, |ui| run_nested_update_func(ui, update_fun));
+  view.borrow_mut().0 = rect;
+
+  r.inner.map(|_| Response(r.response))
+}
+
+/// A simple grid layout. Call end_row() to move to the next row.
+///
+/// Args:
+///     id (str): unique id of the grid
+///
+///     update_fun (Callable[[], None]): your function that draws the cells
+///
+///     num_columns (int): the number of columns, used to make the last column fill the remaining width
+///
+///     striped (bool): color every other row
+///
+///     spacing (tuple[float, float]): horizontal and vertical spacing between cells
+///
+///     min_col_width (float): min width of each column
+///
+///     min_row_height (float): min height of each row
+///
+/// Example::
+///
+///     def form():
+///       label("Name")
+///       text_edit_singleline(name)
+///       end_row()
+///
+///       label("Age")
+///       drag_int(age, 0, 150, 1)
+///       end_row()
+///
+///     grid("form", form, num_columns=2, striped=True)
+#[pyfunction]
+#[pyo3(signature = (id, update_fun, **kwargs))]
+unsafe fn grid(
+  id: &str,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+  let mut g = egui::Grid::new(id);
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(num_columns) = kwargs.get_item("num_columns")? {
+      g = g.num_columns(num_columns.downcast::<PyInt>()?.extract()?);
+    }
+
+    if let Some(striped) = kwargs.get_item("striped")? {
+      g = g.striped(striped.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(spacing) = kwargs.get_item("spacing")? {
+      let (x, y): (f32, f32) = spacing.extract()?;
+      g = g.spacing([x, y]);
+    }
+
+    if let Some(width) = kwargs.get_item("min_col_width")? {
+      g = g.min_col_width(width.extract()?);
+    }
+
+    if let Some(height) = kwargs.get_item("min_row_height")? {
+      g = g.min_row_height(height.extract()?);
+    }
+  }
+
+  g.show(current_ui()?, |ui| run_nested_update_func(ui, update_fun)).inner
+}
+
+/// Move to the next row in a grid layout. Outside of a grid it does nothing.
+///
+/// Example::
+///
+///     def cells():
+///       label("1")
+///       label("2")
+///       end_row()
+///       label("3")
+///       label("4")
+///
+///     grid("numbers", cells)
+#[pyfunction]
+unsafe fn end_row() -> PyResult<()> {
+  let ui = current_ui()?;
+
+  ui.end_row();
+  Ok(())
+}
+
+/// A table with a header row and a scrollable body.
+///
+/// cell_fun is called with the row and column index of every visible cell, so only rows currently on screen
+/// call back into Python. This makes it possible to show millions of rows.
+///
+/// Args:
+///     columns (list[str]): names of the columns shown in the header
+///
+///     num_rows (int): the total number of rows
+///
+///     cell_fun (Callable[[int, int], None]): your function that draws a cell at (row, column)
+///
+///     row_height (float): height of every row. Defaults to the height of a line of text
+///
+///     header_height (float): height of the header row. Defaults to row_height
+///
+///     striped (bool): color every other row
+///
+///     resizable (bool): whether the columns can be resized by the user
+///
+///     max_scroll_height (float): max height of the scrollable body
+///
+///     stick_to_bottom (bool): keep the view scrolled to the bottom when new rows are added
+///
+///     id_salt (str): use it if you have several tables with the same columns
+///
+/// Example::
+///
+///     data = [(i, i * i) for i in range(500_000)]
+///
+///     def cell(row, col):
+///       label(str(data[row][col]))
+///
+///     table(["x", "x squared"], len(data), cell, striped=True)
+#[pyfunction]
+#[pyo3(signature = (columns, num_rows, cell_fun, **kwargs))]
+unsafe fn table(
+  columns: Vec<String>,
+  num_rows: usize,
+  cell_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+
+  show_table(&columns, num_rows, kwargs, |ui, row, col| {
+    run_nested_update_func_with_args(ui, &cell_fun, (row, col))
+  })
+}
+
+/// Show a pandas DataFrame (or a dict of columns) as a striped table with column headers.
+///
+/// Only visible rows are read from the DataFrame, so large frames can be displayed.
+/// It accepts the same keyword arguments as table.
+///
+/// Args:
+///     df (pandas.DataFrame | dict[str, list]): data to show. Cells past the end of a shorter column of a dict are empty
+///
+/// Example::
+///
+///     df = pandas.read_csv("measurements.csv")
+///
+///     def update_func(ctx):
+///       dataframe(df, max_scroll_height=400)
+#[pyfunction]
+#[pyo3(signature = (df, **kwargs))]
+unsafe fn dataframe(
+  df: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+  let py = df.py();
+  let options = PyDict::new(py);
+  options.set_item("striped", true)?;
+  if let Some(kwargs) = kwargs {
+    options.update(kwargs.as_mapping())?;
+  }
+
+  let is_dataframe = df.hasattr("iat")?;
+  let keys = if is_dataframe {
+    df.getattr("columns")?
+  } else {
+    df.call_method0("keys")?
+  };
+  let keys: Vec<Bound<'_, PyAny>> = keys.try_iter()?.collect::<PyResult<_>>()?;
+  let columns: Vec<String> = keys.iter()
+    .map(|key| key.str().map(|s| s.to_string()))
+    .collect::<PyResult<_>>()?;
+
+  let column_lens: Vec<usize> = if is_dataframe {
+    vec![df.len()?; keys.len()]
+  } else {
+    keys.iter()
+      .map(|key| df.get_item(key)?.len())
+      .collect::<PyResult<_>>()?
+  };
+  let num_rows = column_lens.iter().copied().max().unwrap_or(0);
+
+  let iat = if is_dataframe { Some(df.getattr("iat")?) } else { None };
+
+  show_table(&columns, num_rows, Some(&options), |ui, row, col| {
+    // the columns of a dict can have different lengths, the missing cells are empty
+    if row >= column_lens[col] {
+      return Ok(());
+    }
+
+    let value = match &iat {
+      Some(iat) => iat.get_item((row, col))?,
+      None => df.get_item(&keys[col])?.get_item(row)?,
+    };
+    ui.label(value.str()?.to_string());
+    Ok(())
+  })
+}
+
+/// Show a plot with the given items. The plot can be panned by dragging and zoomed with ctrl + scroll.
+///
+/// Returns a PlotResponse with the pointer position in plot coordinates and the point closest to it.
+///
+/// Args:
+///     id (str): unique id of the plot
+///
+///     items (list[Line | Points | BarChart]): what to draw
+///
+///     height (float): height of the plot
+///
+///     width (float): width of the plot
+///
+///     view_aspect (float): width / height ratio of the plot
+///
+///     data_aspect (float): width / height ratio of the data, e.g. 1.0 for equal axes
+///
+///     allow_zoom (bool): whether the plot can be zoomed. Default is True
+///
+///     allow_drag (bool): whether the plot can be panned. Default is True
+///
+///     allow_scroll (bool): whether the plot can be scrolled. Default is True
+///
+///     legend (bool | str): show a legend. Pass "top_left", "top_right", "bottom_left" or "bottom_right" to place it
+///
+///     x_axis_label (str): label of the x axis
+///
+///     y_axis_label (str): label of the y axis
+///
+///     x_axis_formatter (Callable[[float], str]): formats the ticks of the x axis. Its exceptions are raised after the plot is drawn
+///
+///     y_axis_formatter (Callable[[float], str]): formats the ticks of the y axis. Its exceptions are raised after the plot is drawn
+///
+///     auto_bounds (bool): whether to fit the bounds to the data. Default is True
+///
+///     include_x (float): always keep this x value visible
+///
+///     include_y (float): always keep this y value visible
+///
+///     bounds (tuple[float, float, float, float]): show (x_min, x_max, y_min, y_max). Pass it only when you want to move the view, otherwise the plot can't be panned
+///
+/// Example::
+///
+///     sin = Line([(x / 10, math.sin(x / 10)) for x in range(100)], name="sin")
+///     cos = Line([(x / 10, math.cos(x / 10)) for x in range(100)], name="cos", color=(255, 0, 0))
+///
+///     def update_func(ctx):
+///       plot("trigonometry", [sin, cos], height=300)
+#[pyfunction]
+#[pyo3(signature = (id, items, **kwargs))]
+unsafe fn plot(
+  id: &str,
+  items: Vec<Bound<'_, PyAny>>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<PlotResponse> {
+  let mut p = egui_plot::Plot::new(id);
+  let mut set_bounds = None;
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(height) = kwargs.get_item("height")? {
+      p = p.height(height.extract()?);
+    }
+
+    if let Some(width) = kwargs.get_item("width")? {
+      p = p.width(width.extract()?);
+    }
+
+    if let Some(aspect) = kwargs.get_item("view_aspect")? {
+      p = p.view_aspect(aspect.extract()?);
+    }
+
+    if let Some(aspect) = kwargs.get_item("data_aspect")? {
+      p = p.data_aspect(aspect.extract()?);
+    }
+
+    if let Some(allow) = kwargs.get_item("allow_zoom")? {
+      p = p.allow_zoom(allow.downcast::<PyBool>()?.extract::<bool>()?);
+    }
+
+    if let Some(allow) = kwargs.get_item("allow_drag")? {
+      p = p.allow_drag(allow.downcast::<PyBool>()?.extract::<bool>()?);
+    }
+
+    if let Some(allow) = kwargs.get_item("allow_scroll")? {
+      p = p.allow_scroll(allow.downcast::<PyBool>()?.extract::<bool>()?);
+    }
+
+    if let Some(legend) = kwargs.get_item("legend")? {
+      if let Ok(corner) = legend.downcast::<PyString>() {
+        p = p.legend(egui_plot::Legend::default().position(legend_corner(corner.to_str()?)?));
+      } else if legend.downcast::<PyBool>()?.extract()? {
+        p = p.legend(egui_plot::Legend::default());
+      }
+    }
+
+    if let Some(label) = kwargs.get_item("x_axis_label")? {
+      p = p.x_axis_label(label.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(label) = kwargs.get_item("y_axis_label")? {
+      p = p.y_axis_label(label.downcast::<PyString>()?.extract::<String>()?);
+    }
+
+    if let Some(formatter) = kwargs.get_item("x_axis_formatter")? {
+      p = p.x_axis_formatter(axis_formatter(formatter));
+    }
+
+    if let Some(formatter) = kwargs.get_item("y_axis_formatter")? {
+      p = p.y_axis_formatter(axis_formatter(formatter));
+    }
+
+    if let Some(auto_bounds) = kwargs.get_item("auto_bounds")? {
+      p = p.auto_bounds(auto_bounds.downcast::<PyBool>()?.extract::<bool>()?);
+    }
+
+    if let Some(x) = kwargs.get_item("include_x")? {
+      p = p.include_x(x.extract::<f64>()?);
+    }
+
+    if let Some(y) = kwargs.get_item("include_y")? {
+      p = p.include_y(y.extract::<f64>()?);
+    }
+
+    if let Some(bounds) = kwargs.get_item("bounds")? {
+      let (x_min, x_max, y_min, y_max): (f64, f64, f64, f64) = bounds.extract()?;
+      set_bounds = Some(egui_plot::PlotBounds::from_min_max([x_min, y_min], [x_max, y_max]));
+    }
+  }
+
+  let items = items.iter()
+    .map(extract_plot_item)
+    .collect::<PyResult<Vec<_>>>()?;
+
+  let shown = p.show(current_ui()?, |plot_ui| {
+    if let Some(bounds) = set_bounds {
+      plot_ui.set_plot_bounds(bounds);
+    }
+
+    for item in &items {
+      match item {
+        PlotItem::Line(line) => plot_ui.line(line.to_plot_item()),
+        PlotItem::Points(points) => plot_ui.points(points.to_plot_item()),
+        PlotItem::BarChart(chart) => plot_ui.bar_chart(chart.to_plot_item(chart.py())),
+      }
+    }
+
+    let pointer = plot_ui.pointer_coordinate();
+    let nearest = plot_ui.response().hover_pos().and_then(|pos| {
+      items.iter().enumerate()
+        .flat_map(|(item_index, item)| {
+          let points: &[egui_plot::PlotPoint] = match item {
+            PlotItem::Line(line) => &line.points,
+            PlotItem::Points(points) => &points.points,
+            PlotItem::BarChart(_) => &[],
+          };
+          points.iter().enumerate().map(move |(point_index, point)| (item_index, point_index, point))
+        })
+        .map(|(item_index, point_index, point)| {
+          (item_index, point_index, plot_ui.screen_from_plot(*point).distance_sq(pos))
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(item_index, point_index, _)| (item_index, point_index))
+    });
+
+    (pointer, nearest, plot_ui.plot_bounds())
+  });
+  let r = with_formatter_error(Ok(shown))?;
+
+  let (pointer, nearest, bounds) = r.inner;
+  let pointer = pointer.map(|p| (p.x, p.y));
+
+  Ok(PlotResponse {
+    clicked_pos: if r.response.clicked() { pointer } else { None },
+    response: Response(r.response),
+    pointer,
+    nearest,
+    bounds: (bounds.min()[0], bounds.max()[0], bounds.min()[1], bounds.max()[1]),
+  })
+}
+
+unsafe fn show_table(
+  columns: &[String],
+  num_rows: usize,
+  kwargs: Option<&Bound<'_, PyDict>>,
+  mut add_cell: impl FnMut(&mut egui::Ui, usize, usize) -> PyResult<()>
+) -> PyResult<()> {
+  let ui = current_ui()?;
+
+  let mut row_height = ui.text_style_height(&egui::TextStyle::Body) + ui.spacing().item_spacing.y;
+  let mut header_height = None;
+  let mut builder = egui_extras::TableBuilder::new(ui);
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(height) = kwargs.get_item("row_height")? {
+      row_height = height.extract()?;
+    }
+
+    if let Some(height) = kwargs.get_item("header_height")? {
+      header_height = Some(height.extract()?);
+    }
+
+    if let Some(striped) = kwargs.get_item("striped")? {
+      builder = builder.striped(striped.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(resizable) = kwargs.get_item("resizable")? {
+      builder = builder.resizable(resizable.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(height) = kwargs.get_item("max_scroll_height")? {
+      builder = builder.max_scroll_height(height.extract()?);
+    }
+
+    if let Some(stick) = kwargs.get_item("stick_to_bottom")? {
+      builder = builder.stick_to_bottom(stick.downcast::<PyBool>()?.extract()?);
+    }
+
+    if let Some(id_salt) = kwargs.get_item("id_salt")? {
+      builder = builder.id_salt(id_salt.downcast::<PyString>()?.extract::<String>()?);
+    }
+  }
+
+  if let Some((_, rest)) = columns.split_last() {
+    builder = builder
+      .columns(egui_extras::Column::auto(), rest.len())
+      .column(egui_extras::Column::remainder());
+  }
+
+  let mut result = Ok(());
+
+  builder
+    .header(header_height.unwrap_or(row_height), |mut header| {
+      for name in columns {
+        header.col(|ui| {
+          ui.strong(name);
         });
+      }
+    })
+    .body(|body| {
+      body.rows(row_height, num_rows, |mut row| {
+        let index = row.index();
+
+        for col in 0..columns.len() {
+          row.col(|ui| {
+            if result.is_ok() {
+              result = add_cell(ui, index, col);
+            }
+          });
+        }
+      });
+    });
 
-        debug!("Executed update_func");
+  result
+}
 
-        ui_stack.pop().expect(UI_STACK_ERR);
+/// A horizontal bar for menu buttons. Usually placed inside a top_panel.
+///
+/// Example::
+///
+///     def file_menu():
+///       if button_clicked("Open"):
+///         open_file()
+///       if button_clicked("Quit"):
+///         exit()
+///
+///     def menu():
+///       menu_button("File", file_menu)
+///
+///     top_panel("menu", lambda: menu_bar(menu))
+#[pyfunction]
+unsafe fn menu_bar(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
 
-        debug!("Pop UI");
-      });
+  egui::menu::bar(current_ui()?, |ui| run_nested_update_func(ui, update_fun)).inner
+}
 
-    }
+/// A button that opens a menu drawn by update_fun when clicked.
+///
+/// Calling menu_button inside a menu creates a submenu. The menu is closed when a button or a link inside it is clicked,
+/// other widgets like checkboxes keep it open. Call close_menu() to close it yourself.
+///
+/// Example::
+///
+///     def recent_menu():
+///       for path in recent_files:
+///         if button_clicked(path):
+///           open_file(path)
+///
+///     def file_menu():
+///       if button_clicked("Open"):
+///         open_file()
+///       menu_button("Open recent", recent_menu)
+///
+///     menu_button("File", file_menu)
+#[pyfunction]
+unsafe fn menu_button(title: &str, update_fun: Bound<'_, PyAny>) -> PyResult<Response> {
+  let r = current_ui()?.menu_button(title, |ui| {
+    let first_event = ui.ctx().output(|o| o.events.len());
+    SUBMENU_CLICKED.with_borrow_mut(|menus| menus.push(false));
+    let result = run_nested_update_func(ui, update_fun);
+    let submenu_clicked = SUBMENU_CLICKED.with_borrow_mut(|menus| menus.pop()).unwrap_or(false);
+
+    close_menu_on_leaf_click(ui, first_event, submenu_clicked);
+    result
+  });
+
+  // lets the parent menu know that this button opens a submenu and must not close it
+  if r.response.clicked() {
+    SUBMENU_CLICKED.with_borrow_mut(|menus| {
+      if let Some(parent) = menus.last_mut() {
+        *parent = true;
+      }
+    });
   }
+
+  r.inner.unwrap_or(Ok(())).map(|_| Response(r.response))
 }
 
+/// Close the menu we are in (including submenus), if any.
+///
+/// Example::
+///
+///     def file_menu():
+///       checkbox(autosave, "Autosave")
+///       if button_clicked("Done"):
+///         close_menu()
+///
+///     menu_button("File", file_menu)
+#[pyfunction]
+unsafe fn close_menu() -> PyResult<()> {
+  let ui = current_ui()?;
+
+  ui.close_menu();
+  Ok(())
+}
 
-/// Creates a window and runs update_func.
-/// This is an entrypoint for your GUI application.
-/// 
+/// Show an info notification in the corner of the window. Can be called from any thread.
+///
 /// Args:
-///     app_name (str): name displayed at the header bar
+///     text (str): text of the notification
 ///
-///     update_func (Callable[[Context], None]): your function that draws UI
+///     duration (float): how many seconds the notification is shown. Default is 3
 ///
-///     inner_height (float): the desired height of the window
+/// Example::
 ///
-///     inner_width (float): the desired width of the window
+///     if button_clicked("Save"):
+///       save()
+///       toast_info("Saved")
+#[pyfunction]
+#[pyo3(signature = (text, duration = 3.0))]
+fn toast_info(text: String, duration: f64) -> PyResult<()> {
+  push_toast(text, ToastKind::Info, duration)
+}
+
+/// Show a success notification in the corner of the window. Can be called from any thread.
 ///
-///     min_inner_height (float): min height of the window
+/// Args:
+///     text (str): text of the notification
 ///
-///     min_inner_width (float): min width of the window
+///     duration (float): how many seconds the notification is shown. Default is 3
 ///
-///     max_inner_height (float): max height of the window
+/// Example::
 ///
-///     max_inner_width (float): max width of the window
+///     def download():
+///       fetch(url)
+///       toast_success("Downloaded")
 ///
-///     fullscreen (bool): whether to open app in fullscreen
+///     threading.Thread(target=download).start()
+#[pyfunction]
+#[pyo3(signature = (text, duration = 3.0))]
+fn toast_success(text: String, duration: f64) -> PyResult<()> {
+  push_toast(text, ToastKind::Success, duration)
+}
+
+/// Show an error notification in the corner of the window. Can be called from any thread.
 ///
-///     maximized (bool): whether to open app maximized
+/// Args:
+///     text (str): text of the notification
 ///
-///     resizable (bool): whether our app is resizable
+///     duration (float): how many seconds the notification is shown. Default is 3
 ///
-///     transparent (bool): whether our app is transparent
+/// Example::
 ///
-///     icon_path (str): path to icon in rgba format
-/// 
-/// Examples::
+///     try:
+///       load(path.value)
+///     except OSError as e:
+///       toast_error(f"Cannot load: {e}", 5.0)
+#[pyfunction]
+#[pyo3(signature = (text, duration = 3.0))]
+fn toast_error(text: String, duration: f64) -> PyResult<()> {
+  push_toast(text, ToastKind::Error, duration)
+}
+
+/// Show a popup below the widget if the popup is open. Open it with open_popup or toggle_popup.
 ///
-///     name = Str("")
-///     
-///     def update_func(ctx):
-///         heading(f"Hello, {name.value}!")
-///         text_edit_singleline(name)
-///         
-///         if button_clicked("click me"):
-///             print("clicked")
-///     
-///     run_native("My app", update_func)
+/// Args:
+///     id (str): unique id of the popup
+///
+///     response (Response): the widget the popup is attached to
+///
+///     update_fun (Callable[[], None]): your function that draws the popup
+///
+///     close_behavior (str): "click" closes the popup on any click, "click_outside" on clicks outside of it and "ignore" never closes it. Default is "click"
+///
+///     above (bool): show the popup above the widget
+///
+/// Example::
+///
+///     r = button("Pick a color")
+///     if r.clicked():
+///       toggle_popup("colors")
+///
+///     def colors():
+///       selectable_value(color, RED, "red")
+///       selectable_value(color, GREEN, "green")
 ///
+///     popup_below_widget("colors", r, colors)
 #[pyfunction]
-#[pyo3(signature = (app_name, update_func, **kwargs))]
-unsafe fn run_native(
-    app_name: &str,
-    update_func: Bound<'_, PyAny>,
-    kwargs: Option<&Bound<'_, PyDict>>,
+#[pyo3(signature = (id, response, update_fun, **kwargs))]
+unsafe fn popup_below_widget(
+  id: &str,
+  response: &Response,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
 ) -> PyResult<()> {
-  debug!("Trying to get the app lock");
-  // ensure thread safety 
-  let _lock = APP_MUTEX.try_lock().map_err(|_| PyRuntimeError::new_err(APP_MUTEX_ERR))?;
-  // init UI stack
-  debug!("Initialzing UI stack");
-  let mut ui_stack = Vec::with_capacity(32);
-  UI = &raw mut *&mut ui_stack;
-  // parse kwargs
-  let mut viewport = egui::viewport::ViewportBuilder::default();
+  let ui = current_ui()?;
+  let mut close_behavior = egui::PopupCloseBehavior::CloseOnClick;
+  let mut above_or_below = egui::AboveOrBelow::Below;
 
   if let Some(kwargs) = kwargs {
 
-    if let (Some(height), Some(width)) = (kwargs.get_item("inner_height")?, kwargs.get_item("inner_width")?) {
-      viewport = viewport.with_inner_size([
-        width.downcast::<PyInt>()?.extract()?,
-        height.downcast::<PyInt>()?.extract()? 
-      ]); 
+    if let Some(behavior) = kwargs.get_item("close_behavior")? {
+      close_behavior = match behavior.downcast::<PyString>()?.to_str()? {
+        "click" => egui::PopupCloseBehavior::CloseOnClick,
+        "click_outside" => egui::PopupCloseBehavior::CloseOnClickOutside,
+        "ignore" => egui::PopupCloseBehavior::IgnoreClicks,
+        other => return Err(PyValueError::new_err(format!("Unknown close behavior '{}'", other)))
+      };
     }
 
-    if let (Some(height), Some(width)) = (kwargs.get_item("min_inner_height")?, kwargs.get_item("min_inner_width")?) {
-      viewport = viewport.with_min_inner_size([
-        width.downcast::<PyInt>()?.extract()?,
-        height.downcast::<PyInt>()?.extract()? 
-      ]); 
+    if let Some(above) = kwargs.get_item("above")? {
+      if above.downcast::<PyBool>()?.extract()? {
+        above_or_below = egui::AboveOrBelow::Above;
+      }
     }
+  }
 
-    if let (Some(height), Some(width)) = (kwargs.get_item("max_inner_height")?, kwargs.get_item("max_inner_width")?) {
-      viewport = viewport.with_max_inner_size([
-        width.downcast::<PyInt>()?.extract()?,
-        height.downcast::<PyInt>()?.extract()? 
-      ]); 
+  egui::popup::popup_above_or_below_widget(
+    ui,
+    egui::Id::new(id),
+    &response.0,
+    above_or_below,
+    close_behavior,
+    |ui| run_nested_update_func(ui, update_fun)
+  ).unwrap_or(Ok(()))
+}
+
+/// Open the popup with the given id.
+///
+/// Example::
+///
+///     if button_clicked("Show"):
+///       open_popup("my_popup")
+#[pyfunction]
+unsafe fn open_popup(id: &str) -> PyResult<()> {
+  let ui = current_ui()?;
+
+  ui.memory_mut(|m| m.open_popup(egui::Id::new(id)));
+  Ok(())
+}
+
+/// Close the open popup, if any.
+///
+/// Example::
+///
+///     if button_clicked("Cancel"):
+///       close_popup()
+#[pyfunction]
+unsafe fn close_popup() -> PyResult<()> {
+  let ui = current_ui()?;
+
+  ui.memory_mut(|m| m.close_popup());
+  Ok(())
+}
+
+/// Open the popup with the given id if it is closed, close it otherwise.
+///
+/// Example::
+///
+///     if button_clicked("More"):
+///       toggle_popup("more")
+#[pyfunction]
+unsafe fn toggle_popup(id: &str) -> PyResult<()> {
+  let ui = current_ui()?;
+
+  ui.memory_mut(|m| m.toggle_popup(egui::Id::new(id)));
+  Ok(())
+}
+
+/// Returns true if the popup with the given id is open.
+///
+/// Example::
+///
+///     if is_popup_open("more"):
+///       label("popup is shown")
+#[pyfunction]
+unsafe fn is_popup_open(id: &str) -> PyResult<bool> {
+  let ui = current_ui()?;
+
+  Ok(ui.memory(|m| m.is_popup_open(egui::Id::new(id))))
+}
+
+unsafe fn show_side_panel(
+  mut panel: egui::SidePanel,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+  if let Some(kwargs) = kwargs {
+
+    if let Some(resizable) = kwargs.get_item("resizable")? {
+      panel = panel.resizable(resizable.downcast::<PyBool>()?.extract()?);
     }
 
-    if let Some(fullscreen) = kwargs.get_item("fullscreen")? {
-      viewport = viewport.with_fullscreen(fullscreen.downcast::<PyBool>()?.extract()?);
+    if let Some(width) = kwargs.get_item("default_width")? {
+      panel = panel.default_width(width.extract()?);
     }
 
-    if let Some(maximized) = kwargs.get_item("maximized")? {
-      viewport = viewport.with_maximized(maximized.downcast::<PyBool>()?.extract()?);
+    if let Some(width) = kwargs.get_item("min_width")? {
+      panel = panel.min_width(width.extract()?);
+    }
+
+    if let Some(width) = kwargs.get_item("max_width")? {
+      panel = panel.max_width(width.extract()?);
+    }
+
+    if let Some(show) = kwargs.get_item("show_separator_line")? {
+      panel = panel.show_separator_line(show.downcast::<PyBool>()?.extract()?);
     }
+  }
+
+  panel.show_inside(current_ui()?, |ui| run_nested_update_func(ui, update_fun)).inner
+}
+
+/// A panel that covers the left side of the window. Call it before adding any other widgets.
+///
+/// Args:
+///     id (str): unique id of the panel
+///
+///     update_fun (Callable[[], None]): your function that draws the panel
+///
+///     resizable (bool): whether the panel can be resized by the user. Default is True
+///
+///     default_width (float): the initial width of the panel
+///
+///     min_width (float): min width of the panel
+///
+///     max_width (float): max width of the panel
+///
+///     show_separator_line (bool): whether to show a separator line between the panel and the content
+///
+/// Example::
+///
+///     def navigation():
+///       heading("Pages")
+///       selectable_value(page, HOME, "Home")
+///       selectable_value(page, SETTINGS, "Settings")
+///
+///     side_panel_left("navigation", navigation, default_width=150)
+///     heading("Content goes here")
+#[pyfunction]
+#[pyo3(signature = (id, update_fun, **kwargs))]
+unsafe fn side_panel_left(
+  id: &str,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+
+  show_side_panel(egui::SidePanel::left(egui::Id::new(id)), update_fun, kwargs)
+}
+
+/// A panel that covers the right side of the window. Call it before adding any other widgets.
+///
+/// It accepts the same arguments as side_panel_left.
+///
+/// Example::
+///
+///     side_panel_right("properties", lambda: label("Properties"), resizable=False)
+#[pyfunction]
+#[pyo3(signature = (id, update_fun, **kwargs))]
+unsafe fn side_panel_right(
+  id: &str,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+
+  show_side_panel(egui::SidePanel::right(egui::Id::new(id)), update_fun, kwargs)
+}
+
+unsafe fn show_top_bottom_panel(
+  mut panel: egui::TopBottomPanel,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+  if let Some(kwargs) = kwargs {
 
     if let Some(resizable) = kwargs.get_item("resizable")? {
-      viewport = viewport.with_resizable(resizable.downcast::<PyBool>()?.extract()?);
+      panel = panel.resizable(resizable.downcast::<PyBool>()?.extract()?);
     }
 
-    if let Some(transparent) = kwargs.get_item("transparent")? {
-      viewport = viewport.with_transparent(transparent.downcast::<PyBool>()?.extract()?);
+    if let Some(height) = kwargs.get_item("default_height")? {
+      panel = panel.default_height(height.extract()?);
     }
 
-    if let Some(icon_path) = kwargs.get_item("icon_path")? {
-      let path = icon_path.downcast::<PyString>()?.extract::<String>()?;
-      let buf = fs::read(path)?;
+    if let Some(height) = kwargs.get_item("min_height")? {
+      panel = panel.min_height(height.extract()?);
+    }
+
+    if let Some(height) = kwargs.get_item("max_height")? {
+      panel = panel.max_height(height.extract()?);
+    }
 
-      let icon_data = eframe::icon_data::from_png_bytes(&buf)
-        .map_err(|e| PyOSError::new_err(format!("Failed to decode png file: {}", e)))?;
-      viewport = viewport.with_icon(icon_data);
+    if let Some(height) = kwargs.get_item("exact_height")? {
+      panel = panel.exact_height(height.extract()?);
+    }
+
+    if let Some(show) = kwargs.get_item("show_separator_line")? {
+      panel = panel.show_separator_line(show.downcast::<PyBool>()?.extract()?);
     }
   }
 
-  let options = eframe::NativeOptions {
-    viewport,
-    ..eframe::NativeOptions::default()
-  };
-  debug!("Creating a window");
-  // create a window
-  let result = eframe::run_native(
-        app_name,
-        options,
-        Box::new(|cc| {
-            // This gives us image support:
-            egui_extras::install_image_loaders(&cc.egui_ctx);
+  panel.show_inside(current_ui()?, |ui| run_nested_update_func(ui, update_fun)).inner
+}
 
-            Ok(Box::new(PyeguiApp { update_func: update_func }))
-        }),
-  );
+/// A panel that covers the top of the window, e.g. a menu bar. Call it before adding any other widgets.
+///
+/// Args:
+///     id (str): unique id of the panel
+///
+///     update_fun (Callable[[], None]): your function that draws the panel
+///
+///     resizable (bool): whether the panel can be resized by the user. Default is False
+///
+///     default_height (float): the initial height of the panel
+///
+///     min_height (float): min height of the panel
+///
+///     max_height (float): max height of the panel
+///
+///     exact_height (float): fixed height of the panel
+///
+///     show_separator_line (bool): whether to show a separator line between the panel and the content
+///
+/// Example::
+///
+///     top_panel("toolbar", lambda: button_clicked("Open"))
+///     heading("Content goes here")
+#[pyfunction]
+#[pyo3(signature = (id, update_fun, **kwargs))]
+unsafe fn top_panel(
+  id: &str,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+
+  show_top_bottom_panel(egui::TopBottomPanel::top(egui::Id::new(id)), update_fun, kwargs)
+}
+
+/// A panel that covers the bottom of the window, e.g. a status bar. Call it before adding any other widgets.
+///
+/// It accepts the same arguments as top_panel.
+///
+/// Example::
+///
+///     bottom_panel("status", lambda: label("Ready"))
+#[pyfunction]
+#[pyo3(signature = (id, update_fun, **kwargs))]
+unsafe fn bottom_panel(
+  id: &str,
+  update_fun: Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<()> {
+
+  show_top_bottom_panel(egui::TopBottomPanel::bottom(egui::Id::new(id)), update_fun, kwargs)
+}
+
+/// Control float with a slider.
+///
+/// Args:
+///     prefix (str): text shown before the value
+///
+///     suffix (str): text shown after the value
+///
+///     step_by (float): the value is rounded to multiples of this step
+///
+///     logarithmic (bool): use a logarithmic scale
+///
+///     show_value (bool): whether to show the value next to the slider. Default is True
+///
+///     clamping (str): "never", "edits" to clamp only values entered by the user or "always". Default is "always"
+///
+///     formatter (Callable[[float], str]): your function that formats the shown value. Its exceptions are raised after the widget is drawn
+///
+///     vertical (bool): show a vertical slider
+///
+/// Example::
+///
+///     data = Float(5) 
+///     # inside update_func 
+///     slider_float(data, 0, 50, "slide me")
+///     slider_float(data, 0, 50, "volume", suffix="%", step_by=5)
+///     slider_float(data, 0, 50, "delay", formatter=lambda v: f"{v:.1f} ms")
+///
+///     with horizontal():
+///       for channel in channels:
+///         slider_float(channel, 0, 1, "", vertical=True)
+#[pyfunction]
+#[pyo3(signature = (value, min, max, text, **kwargs))]
+unsafe fn slider_float(
+  value: &mut Float,
+  min: f32,
+  max: f32,
+  text: &str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let slider = egui::Slider::new(&mut value.value, min..=max).text(text);
 
-  match result {
-    Ok(_) => Ok(()),
-    Err(err) => Err(PyRuntimeError::new_err(format!("Cannot create a window: {}", err.to_string())))
-  }
+  with_formatter_error(add_widget(ui, slider_options(slider, kwargs)?, kwargs))
 }
 
-// helpers
+/// Control int with a slider. Accepts the same arguments as slider_float.
+/// 
+/// Example::
+///
+///     data = Int(5) 
+///     # inside update_func 
+///     slider_int(data, 0, 50, "slide me")
+#[pyfunction]
+#[pyo3(signature = (value, min, max, text, **kwargs))]
+unsafe fn slider_int(
+  value: &mut Int,
+  min: i32,
+  max: i32,
+  text: &str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let slider = egui::Slider::new(&mut value.value, min..=max).text(text).integer();
 
-unsafe fn ui_stack(ui: &*mut Vec<*mut egui::Ui>) -> PyResult<&mut Vec<*mut egui::Ui>> {
-    ui.as_mut().ok_or(PyRuntimeError::new_err(UI_CALL_OUTSIDE_UPDATE_FUNC))
+  with_formatter_error(add_widget(ui, slider_options(slider, kwargs)?, kwargs))
 }
 
-unsafe fn last_ui(ui_stack: &mut Vec<*mut egui::Ui>) -> PyResult<&mut egui::Ui> {
-  let last_ui = ui_stack.last_mut().ok_or(PyRuntimeError::new_err(UI_STACK_ERR))?;
 
-  last_ui.as_mut().ok_or(PyRuntimeError::new_err(UI_PTR_NULL_ERR))
-}
+/// Control float by dragging the number.
+///
+/// Args:
+///     prefix (str): text shown before the value
+///
+///     suffix (str): text shown after the value
+///
+///     fixed_decimals (int): always show this many decimals
+///
+///     formatter (Callable[[float], str]): your function that formats the shown value. Its exceptions are raised after the widget is drawn
+///
+///     parser (Callable[[str], float | None]): your function that parses the value typed by the user. Return None or raise for invalid input
+///
+/// Example::
+///
+///     data = Float(5) 
+///     # inside update_func 
+///     drag_float(data, 0, 50, 1.5)
+///     drag_float(data, 0, 50, 1.5, suffix=" px")
+///     drag_float(ratio, 0, 1, 0.01, formatter=lambda v: f"{v * 100:.0f}%", parser=lambda s: float(s.rstrip("%")) / 100)
+#[pyfunction]
+#[pyo3(signature = (value, min, max, speed, **kwargs))]
+unsafe fn drag_float(
+  value: &mut Float,
+  min: f32,
+  max: f32,
+  speed: f32,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let drag = egui::DragValue::new(&mut value.value).speed(speed).range(min..=max);
 
-unsafe fn current_ui(ui: &*mut Vec<*mut egui::Ui>) -> PyResult<&mut egui::Ui> {
-  last_ui(ui_stack(ui)?)  
+  with_formatter_error(add_widget(ui, drag_options(drag, kwargs)?, kwargs))
 }
 
-unsafe fn run_nested_update_func(ui: &mut egui::Ui, update_fun: Bound<'_, PyAny>) -> PyResult<()> {
-  let ui_stack = ui_stack(&UI).unwrap_unchecked();
-
-  ui_stack.push(&raw mut *ui);
-
-  if let Err(err) = update_fun.call0() {
-    Python::with_gil(|py| {
-      err.display(py);
-    });
-  }
-
-  match ui_stack.pop() {
-    Some(_) => Ok(()),
-    None => Err(PyRuntimeError::new_err(UI_STACK_ERR))
-  }
-} 
-
-// UI functions
-
-/// Show large text
+/// Control int by dragging the number. Accepts the same arguments as drag_float.
 ///
 /// Example::
 ///
-///     heading("hello") 
+///     data = Int(5) 
+///     # inside update_func 
+///     drag_int(data, 0, 50, 1)
 #[pyfunction]
-unsafe fn heading(text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (value, min, max, speed, **kwargs))]
+unsafe fn drag_int(
+  value: &mut Int,
+  min: i32,
+  max: i32,
+  speed: i32,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let drag = egui::DragValue::new(&mut value.value).speed(speed).range(min..=max);
 
-  ui.heading(text);
-  Ok(())
+  with_formatter_error(add_widget(ui, drag_options(drag, kwargs)?, kwargs))
 }
 
-/// Show monospace (fixed width) text.
+/// A clickable hyperlink
+/// 
+/// Example::
 ///
+///     hyperlink("https://github.com/emilk/egui")
+#[pyfunction]
+#[pyo3(signature = (url, **kwargs))]
+unsafe fn hyperlink(url: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  
+  add_widget(ui, egui::Hyperlink::new(url), kwargs)
+}
+
+/// A clickable hyperlink with label
+/// 
 /// Example::
 ///
-///     monospace("hello") 
+///     hyperlink_to("egui on GitHub", "https://www.github.com/emilk/egui/")
 #[pyfunction]
-unsafe fn monospace(text: &str) -> PyResult<()>  {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (label, url, **kwargs))]
+unsafe fn hyperlink_to(label: &str, url: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  
+  add_widget(ui, egui::Hyperlink::from_label_and_url(label, url), kwargs)
+}
 
-  ui.monospace(text);
-  Ok(())
+
+/// Clickable text, that looks like a hyperlink.
+/// To link to a web page, use hyperlink or hyperlink_to.
+/// 
+/// Example::
+///
+///     if link_clicked("egui on GitHub"):
+///       print("clicked on a fake link")
+#[pyfunction]
+unsafe fn link_clicked(label: &str) -> PyResult<bool> {
+  let ui = current_ui()?;
+  
+  Ok(ui.link(label).clicked())
 }
 
-/// Show small text.
+/// Clickable text, that looks like a hyperlink. Returns its Response
 ///
 /// Example::
 ///
-///     small("hello") 
+///     if link("egui on GitHub").secondary_clicked():
+///       print("right clicked on a fake link")
 #[pyfunction]
-unsafe fn small(text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (label, **kwargs))]
+unsafe fn link(label: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
 
-  ui.small(text);
-  Ok(())
+  add_widget(ui, egui::Link::new(label), kwargs)
 }
 
-/// Show text that stand out a bit (e.g. slightly brighter).
+/// Show a checkbox.
 ///
+/// Args:
+///     indeterminate (bool): show the checkbox as partially checked. Clicking it still toggles the value
+/// 
 /// Example::
 ///
-///     strong("hello") 
+///     data = Bool(false)
+///     # inside update_func
+///     checkbox(data, "check me")
+///
+///     all_checked = Bool(all(item.value for item in items))
+///     some_checked = any(item.value for item in items)
+///     if checkbox(all_checked, "select all", indeterminate=some_checked and not all_checked.value).changed():
+///       for item in items:
+///         item.value = all_checked.value
 #[pyfunction]
-unsafe fn strong(text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (checked, text, **kwargs))]
+unsafe fn checkbox(checked: &mut Bool, text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let mut w = egui::Checkbox::new(&mut checked.value, text);
 
-  ui.strong(text);
-  Ok(())
+  if let Some(kwargs) = kwargs {
+
+    if let Some(indeterminate) = kwargs.get_item("indeterminate")? {
+      w = w.indeterminate(indeterminate.downcast::<PyBool>()?.extract()?);
+    }
+  }
+  
+  add_widget(ui, w, kwargs)
 }
 
-/// Show text that is weaker (fainter color).
-///
+/// Acts like a checkbox, but looks like a selectable label.
+/// 
 /// Example::
 ///
-///     weak("hello") 
+///     data = Bool(false)
+///     # inside update_func
+///     toggle_value(data, "check me")
 #[pyfunction]
-unsafe fn weak(text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (selected, text, **kwargs))]
+unsafe fn toggle_value(selected: &mut Bool, text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  
+  let mut r = add_widget(ui, egui::SelectableLabel::new(selected.value, text), kwargs)?;
+  if r.0.clicked() {
+    selected.value = !selected.value;
+    r.0.mark_changed();
+  }
 
-  ui.weak(text);
-  Ok(())
+  Ok(r)
 }
 
-/// Show some text.
+
+/// Show a radio button. It is selected if current_value == selected_value. If clicked, selected_value is assigned to current_value.
 ///
-/// Example::
+/// current_value can be Int, Str or any object with a value attribute, alternative can be any value comparable with it.
 /// 
-///     label("some text") 
+/// Example::
+///
+///     RED = 0
+///     GREEN = 1
+///     BLUE = 2
+///     
+///     c = Int(RED)
+///     
+///     radio_value(c, RED, "red")
+///     radio_value(c, GREEN, "green")
+///     radio_value(c, BLUE, "blue")
+///
+///     mode = Str("fast")
+///
+///     radio_value(mode, "fast", "Fast")
+///     radio_value(mode, "precise", "Precise")
 #[pyfunction]
-unsafe fn label(text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (current_value, alternative, text, **kwargs))]
+unsafe fn radio_value(
+  current_value: &Bound<'_, PyAny>,
+  alternative: &Bound<'_, PyAny>,
+  text: &str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let selected = current_value.getattr("value")?.eq(alternative)?;
+  
+  let mut r = add_widget(ui, egui::RadioButton::new(selected, text), kwargs)?;
+  if r.0.clicked() && !selected {
+    current_value.setattr("value", alternative)?;
+    r.0.mark_changed();
+  }
 
-  ui.label(text);
-  Ok(())
+  Ok(r)
 }
 
-/// Show text as monospace with a gray background.
+
+/// Show selectable text. It is selected if current_value == selected_value. If clicked, selected_value is assigned to current_value.
 ///
+/// current_value can be Int, Str or any object with a value attribute, alternative can be any value comparable with it.
+/// 
 /// Example::
 ///
-///     code("print(42 + 27)") 
+///     RED = 0
+///     GREEN = 1
+///     BLUE = 2
+///     
+///     c = Int(RED)
+///     
+///     selectable_value(c, RED, "red")
+///     selectable_value(c, GREEN, "green")
+///     selectable_value(c, BLUE, "blue")
 #[pyfunction]
-unsafe fn code(text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (current_value, alternative, text, **kwargs))]
+unsafe fn selectable_value(
+  current_value: &Bound<'_, PyAny>,
+  alternative: &Bound<'_, PyAny>,
+  text: &str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let selected = current_value.getattr("value")?.eq(alternative)?;
+  
+  let mut r = add_widget(ui, egui::SelectableLabel::new(selected, text), kwargs)?;
+  if r.0.clicked() && !selected {
+    current_value.setattr("value", alternative)?;
+    r.0.mark_changed();
+  }
 
-  ui.code(text);
-  Ok(())
+  Ok(r)
 }
 
-/// Show singleline text field and update the text
+/// Shows a combo box with values defined in "alternatives" and their corresponding names
+/// defined in "names"
+/// 
+/// Example::
+///
+///     RED = 0
+///     GREEN = 1
+///     BLUE = 2
+///
+///     data = Int(RED)
+///
+///     def update_func(a):
+///         combo_box(data, [RED, GREEN, BLUE], ["red", "green", "blue"], "choose your fate")
+///
+/// All combo boxes (combo_box, combo_box_str and combo_box_search) accept these arguments:
+///
+/// Args:
+///     id_salt (str): id of the combo box. Use it when several combo boxes have the same or an empty label
+///
+///     width (float): width of the combo box
+///
+///     truncate (bool): truncate the selected text instead of wrapping it
+///
+///     icon (str): text drawn instead of the default arrow icon, e.g. "⏷"
 ///
 /// Example::
 ///
-///     text = Str("print(42 + 27)")
-///     # inside update func
-///     code_editor(text)
+///     for i, row in enumerate(rows):
+///         combo_box_str(row.kind, kinds, "", id_salt=f"kind{i}", width=80)
 #[pyfunction]
-unsafe fn code_editor(text: &mut Str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (current_value, alternatives, names, label, **kwargs))]
+unsafe fn combo_box(
+  current_value: &mut Int,
+  alternatives: Vec<i32>,
+  names: Vec<String>,
+  label: &str,
+  kwargs: Option<&Bound<'_, PyDict>>
+) -> PyResult<Response> {
+  let ui = current_ui()?;
 
-  ui.code_editor(&mut text.value);
-  Ok(())
+  let r = combo_box_options(label, kwargs)?
+    .selected_text(names.get(current_value.value.try_into().unwrap_or(0)).unwrap_or(&"Unknown".to_string()))
+    .show_ui(ui, |ui| {
+      for (i, alternative) in alternatives.iter().enumerate() {
+        ui.selectable_value(
+          &mut current_value.value, 
+          *alternative, 
+          names.get(i).unwrap_or(&"Unknown".to_string())
+        );
+      }
+    }
+  );
+  Ok(Response(r.response))
 }
 
-/// Show singleline text field and update the text
+/// Shows a combo box with the given options. The selected option is stored in current_value
 ///
 /// Example::
 ///
-///     text = Str("editable")
-///     # inside update func
-///     text_edit_singleline(text, hint_text="hint me bro")
+///     fruit = Str("apple")
+///
+///     def update_func(ctx):
+///         combo_box_str(fruit, ["apple", "banana", "cherry"], "fruit")
 #[pyfunction]
-#[pyo3(signature = (text, **kwargs))]
-unsafe fn text_edit_singleline(
-  text: &mut Str,
+#[pyo3(signature = (current_value, options, label, **kwargs))]
+unsafe fn combo_box_str(
+  current_value: &mut Str,
+  options: Vec<String>,
+  label: &str,
   kwargs: Option<&Bound<'_, PyDict>>
-) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-
-  let mut w = egui::TextEdit::singleline(&mut text.value);
+) -> PyResult<Response> {
+  let ui = current_ui()?;
 
-  if let Some(kwargs) = kwargs {
+  let mut r = combo_box_options(label, kwargs)?
+    .selected_text(current_value.value.as_str())
+    .show_ui(ui, |ui| {
+      let mut changed = false;
 
-    if let Some(hint_text) = kwargs.get_item("hint_text")? {
-      w = w.hint_text(hint_text.downcast::<PyString>()?.extract::<String>()?);
+      for option in options {
+        if ui.selectable_label(current_value.value == option, &option).clicked() && current_value.value != option {
+          current_value.value = option;
+          changed = true;
+        }
+      }
+      changed
     }
+  );
 
+  if r.inner == Some(true) {
+    r.response.mark_changed();
   }
-
-  ui.add(w);
-  Ok(())
+  Ok(Response(r.response))
 }
 
-/// Show multiline text field and update the text
-/// 
+/// Shows a combo box with a filter text field in its popup. Only options containing the filter text are shown,
+/// so it stays usable with thousands of options.
+///
 /// Example::
 ///
-///     text = Str("editable")
-///     # inside update func
-///     text_edit_multiline(text, hint_text="hint")
+///     country = Str("")
+///
+///     def update_func(ctx):
+///         combo_box_search(country, countries, "country")
 #[pyfunction]
-#[pyo3(signature = (text, **kwargs))]
-unsafe fn text_edit_multiline(
-  text: &mut Str,
+#[pyo3(signature = (current_value, options, label, **kwargs))]
+unsafe fn combo_box_search(
+  current_value: &mut Str,
+  options: Vec<String>,
+  label: &str,
   kwargs: Option<&Bound<'_, PyDict>>
-) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let id_salt = match kwargs {
+    Some(kwargs) => kwargs.get_item("id_salt")?.map(|salt| salt.extract::<String>()).transpose()?,
+    None => None,
+  };
+  let filter_id = ui.make_persistent_id(id_salt.as_deref().unwrap_or(label)).with("filter");
 
-  let mut w = egui::TextEdit::multiline(&mut text.value);
+  let mut r = combo_box_options(label, kwargs)?
+    .selected_text(current_value.value.as_str())
+    .height(f32::INFINITY)
+    .show_ui(ui, |ui| {
+      let mut filter = ui.data_mut(|d| d.get_temp::<String>(filter_id)).unwrap_or_default();
+      let filter_r = ui.add(egui::TextEdit::singleline(&mut filter).hint_text("Search"));
+      if ui.memory(|m| m.focused().is_none()) {
+        filter_r.request_focus();
+      }
 
-  if let Some(kwargs) = kwargs {
+      let needle = filter.to_lowercase();
+      let matches: Vec<&String> = options.iter().filter(|o| o.to_lowercase().contains(&needle)).collect();
+      let row_height = ui.spacing().interact_size.y;
+      let mut changed = false;
 
-    if let Some(hint_text) = kwargs.get_item("hint_text")? {
-      w = w.hint_text(hint_text.downcast::<PyString>()?.extract::<String>()?);
+      egui::ScrollArea::vertical().max_height(250.0).show_rows(ui, row_height, matches.len(), |ui, rows| {
+        for option in &matches[rows] {
+          if ui.selectable_label(current_value.value == **option, option.as_str()).clicked() && current_value.value != **option {
+            current_value.value = (*option).clone();
+            changed = true;
+          }
+        }
+      });
+
+      ui.data_mut(|d| d.insert_temp(filter_id, filter));
+      changed
     }
+  );
 
+  if r.inner == Some(true) {
+    r.response.mark_changed();
   }
-
-  ui.add(w);
-  Ok(())
-}
-
-/// Returns true if the button was clicked this frame
-/// 
-/// if button_clicked("click me"):
-///   print("click me, my friend")
-#[pyfunction]
-unsafe fn button_clicked(text: &str) -> PyResult<bool> {
-  let ui = current_ui(&UI)?;
-
-  Ok(ui.button(text).clicked())
-}
-
-/// Returns true if the small button was clicked this frame
-/// 
-/// if small_button_clicked("click me"):
-///   print("click me, my friend")
-#[pyfunction]
-unsafe fn small_button_clicked(text: &str) -> PyResult<bool> {
-  let ui = current_ui(&UI)?;
-
-  Ok(ui.small_button(text).clicked())
+  Ok(Response(r.response))
 }
 
-/// Start a ui with horizontal layout. After you have called this, the function registers the contents as any other widget.
-/// 
-/// Elements will be centered on the Y axis, i.e. adjusted up and down to lie in the center of the horizontal layout. The initial height is style.spacing.interact_size.y. Centering is almost always what you want if you are planning to mix widgets or use different types of text.
-/// 
-/// If you don’t want the contents to be centered, use horizontal_top instead.
-/// 
+/// A simple progress bar.
+/// value in the [0, 1] range, where 1 means “completed”.
+///
 /// Example::
 ///
-///     def horizontal_update_func():
-///       heading("I'm horizontal")
-///     
-///     horizontal(horizontal_update_func)
+///     progress(0.5)
 #[pyfunction]
-unsafe fn horizontal(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
-
-  current_ui(&UI)?.horizontal(|ui| run_nested_update_func(ui, update_fun)).inner
+#[pyo3(signature = (value, **kwargs))]
+unsafe fn progress(value: f32, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  
+  add_widget(ui, egui::widgets::ProgressBar::new(value).show_percentage(), kwargs)
 }
 
-/// Like horizontal, but allocates the full vertical height and then centers elements vertically.
-#[pyfunction]
-unsafe fn horizontal_centered(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
 
-  current_ui(&UI)?.horizontal_centered(|ui| run_nested_update_func(ui, update_fun)).inner
-}
-/// Like horizontal, but aligns content with top.
+/// A spinner widget used to indicate loading.
+///
+/// Example::
+///
+///     spinner()
 #[pyfunction]
-unsafe fn horizontal_top(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
-
-  current_ui(&UI)?.horizontal_top(|ui| run_nested_update_func(ui, update_fun)).inner
+#[pyo3(signature = (**kwargs))]
+unsafe fn spinner(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  
+  add_widget(ui, egui::Spinner::new(), kwargs)
 }
 
-/// Start a ui with horizontal layout that wraps to a new row when it reaches the right edge of the max_size. After you have called this, the function registers the contents as any other widget.
+/// Shows a button with the given color. If the user clicks the button, a full color picker is shown.
 /// 
-/// Elements will be centered on the Y axis, i.e. adjusted up and down to lie in the center of the horizontal layout. The initial height is style.spacing.interact_size.y. Centering is almost always what you want if you are planning to mix widgets or use different types of text.
+/// Example::
+///
+///     color = RGB(69, 69, 69)
+///     # inside udpate_func
+///     color_edit_button_rgb(color)
+///     heading(f"r:{color.r} g:{color.g} b:{color.b}")
 #[pyfunction]
-unsafe fn horizontal_wrapped(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
+unsafe fn color_edit_button_rgb(rgb: &mut RGB) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  let mut tmp: [f32; 3] = [rgb.r, rgb.g, rgb.b];
+
+  let r = ui.color_edit_button_rgb(&mut tmp);
+
+  rgb.r = tmp[0];
+  rgb.g = tmp[1];
+  rgb.b = tmp[2];
 
-  current_ui(&UI)?.horizontal_wrapped(|ui| run_nested_update_func(ui, update_fun)).inner
+  Ok(Response(r))
 }
 
 
-/// A CollapsingHeader that starts out collapsed.
+/// Shows a button with the given color stored as a list of 3 integers in the 0-255 range. If the user clicks the button, a full color picker is shown.
+/// The list is updated in place.
 ///
 /// Example::
 ///
-///     def update_func():
-///       heading("hi")
-///     collapsing("collapsed", update_func)
+///     color = [255, 128, 0]
+///     # inside update_func
+///     color_edit_button_srgb(color)
+///     heading(f"r:{color[0]} g:{color[1]} b:{color[2]}")
 #[pyfunction]
-unsafe fn collapsing(heading: &str, update_fun: Bound<'_, PyAny>) -> PyResult<()> {
+unsafe fn color_edit_button_srgb(srgb: &Bound<'_, PyList>) -> PyResult<Response> {
+  let ui = current_ui()?;
 
-  current_ui(&UI)?.collapsing(heading, |ui| run_nested_update_func(ui, update_fun));
-  Ok(())
-}
+  let mut tmp: [u8; 3] = srgb.extract::<Vec<u8>>()?
+    .try_into()
+    .map_err(|_| PyValueError::new_err(SRGB_LEN_ERR))?;
 
-/// Create a child ui which is indented to the right.
-/// Example::
-///
-///     def update_func():
-///       heading("I'm indented")
-///     indent(update_func)
-#[pyfunction]
-unsafe fn indent(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
+  let r = ui.color_edit_button_srgb(&mut tmp);
 
-  current_ui(&UI)?.indent("your mom", |ui| run_nested_update_func(ui, update_fun)).inner
+  for (i, c) in tmp.into_iter().enumerate() {
+    srgb.set_item(i, c)?;
+  }
+
+  Ok(Response(r))
 }
 
-/// Visually groups the contents together.
+/// Shows a button with the given color and transparency. If the user clicks the button, a full color picker is shown.
+///
+/// Args:
+///     alpha_mode (str): "blend" edits normal transparency, "blend_or_additive" also allows additive colors and "opaque" hides alpha. Default is "blend"
 ///
 /// Example::
 ///
-///     def update_func():
-///       heading("hi")
-///       heading("there")
-///     
-///     group(update_func)
+///     color = RGBA(1.0, 0.0, 0.0, 0.5)
+///     # inside update_func
+///     color_edit_button_rgba(color)
+///     heading(f"alpha: {color.a}")
 #[pyfunction]
-unsafe fn group(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
+#[pyo3(signature = (rgba, alpha_mode = "blend"))]
+unsafe fn color_edit_button_rgba(rgba: &mut RGBA, alpha_mode: &str) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  let alpha = color_alpha_mode(alpha_mode)?;
+  let mut tmp = egui::Rgba::from_rgba_unmultiplied(rgba.r, rgba.g, rgba.b, rgba.a);
 
-  current_ui(&UI)?.group(|ui| run_nested_update_func(ui, update_fun)).inner
+  let r = egui::color_picker::color_edit_button_rgba(ui, &mut tmp, alpha);
+
+  [rgba.r, rgba.g, rgba.b, rgba.a] = tmp.to_rgba_unmultiplied();
+
+  Ok(Response(r))
 }
 
-/// Create a scoped child ui.
-/// 
-/// You can use this to temporarily change the Style of a sub-region.
+/// Shows a button with the given HSVA color. If the user clicks the button, a full color picker is shown.
+///
+/// Args:
+///     alpha_mode (str): "blend" edits normal transparency, "blend_or_additive" also allows additive colors and "opaque" hides alpha. Default is "blend"
 ///
 /// Example::
 ///
-///     def update_func():
-///       heading("0.5 opacity")
-///       set_opacity(0.5)
-///     
-///     heading("normal opacity")
-///     scope(update_func)
+///     color = HSVA(0.0, 1.0, 1.0)
+///     # inside update_func
+///     color_edit_button_hsva(color)
+///     heading(f"hue: {color.h:.2f}")
 #[pyfunction]
-unsafe fn scope(update_fun: Bound<'_, PyAny>) -> PyResult<()> {
+#[pyo3(signature = (hsva, alpha_mode = "blend"))]
+unsafe fn color_edit_button_hsva(hsva: &mut HSVA, alpha_mode: &str) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  let alpha = color_alpha_mode(alpha_mode)?;
+  let mut tmp = hsva.to_hsva();
+
+  let r = egui::color_picker::color_edit_button_hsva(ui, &mut tmp, alpha);
 
-  current_ui(&UI)?.scope(|ui| run_nested_update_func(ui, update_fun)).inner
+  *hsva = tmp.into();
+
+  Ok(Response(r))
 }
 
-/// Control float with a slider.
+/// Show an image available at the given uri.
+///
+/// Svg images are rasterized at the size they are shown with, so they stay sharp when scaled.
+///
+/// Args:
+///     max_height (int): max height of the image
+///
+///     max_width (int): max width of the image
+///
+///     max_size (tuple[float, float]): max width and height of the image
+///
+///     fit_to_exact_size (tuple[float, float]): scale the image to exactly this size
+///
+///     maintain_aspect_ratio (bool): whether to keep the aspect ratio when scaling. Default is True
+///
+///     corner_radius (float): round the corners of the image
+///
+///     tint (Color | str | tuple[int, int, int]): multiply the image with this color
+///
+///     bg_fill (Color | str | tuple[int, int, int]): color behind transparent parts of the image
+///
+///     sense (str): which interactions the image reacts to: "hover", "click", "drag" or "click_and_drag". Default is "hover"
 ///
 /// Example::
 ///
-///     data = Float(5) 
-///     # inside update_func 
-///     slider_float(data, 0, 50, "slide me")
+///     image("https://picsum.photos/480")
+///     image("file://assets/ferris.png", max_height = 50, max_width = 50)
+///     image("file://assets/avatar.png", fit_to_exact_size=(64, 64), corner_radius=32)
+///     image("file://icons/save.svg", fit_to_exact_size=(24, 24))
 #[pyfunction]
-unsafe fn slider_float(value: &mut Float, min: f32, max: f32, text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (source, **kwargs))]
+unsafe fn image(
+  source: &str, 
+  kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Response> {
+  let ui = current_ui()?;
   
-  ui.add(egui::Slider::new(&mut value.value, min..=max).text(text));
-  Ok(())
+  add_widget(ui, image_options(egui::Image::new(source), kwargs)?, kwargs)
 }
 
-/// Control int with a slider.
-/// 
+/// Show an image that reacts to clicks and return True if it was clicked this frame. Accepts the same arguments as image.
+///
 /// Example::
 ///
-///     data = Int(5) 
-///     # inside update_func 
-///     slider_int(data, 0, 50, "slide me")
+///     if image_clicked("file://thumbnails/cat.png", max_width=100):
+///         open_picture("cat.png")
 #[pyfunction]
-unsafe fn slider_int(value: &mut Int, min: i32, max: i32, text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.add(egui::Slider::new(&mut value.value, min..=max).text(text).integer());
-  Ok(())
-}
+#[pyo3(signature = (source, **kwargs))]
+unsafe fn image_clicked(source: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<bool> {
+  let ui = current_ui()?;
 
+  let img = image_options(egui::Image::new(source).sense(egui::Sense::click()), kwargs)?;
+  Ok(add_widget(ui, img, kwargs)?.0.clicked())
+}
 
-/// Control float by dragging the number.
+/// Show an image and return True if the pointer is over it. Accepts the same arguments as image.
 ///
 /// Example::
 ///
-///     data = Float(5) 
-///     # inside update_func 
-///     drag_float(data, 0, 50, 1.5)
+///     if image_hovered("file://thumbnails/cat.png", max_width=100):
+///         label("cat.png")
 #[pyfunction]
-unsafe fn drag_float(value: &mut Float, min: f32, max: f32, speed: f32) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
- 
-  ui.add(egui::DragValue::new(&mut value.value).speed(speed).range(min..=max));
-  Ok(())
+#[pyo3(signature = (source, **kwargs))]
+unsafe fn image_hovered(source: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<bool> {
+  let ui = current_ui()?;
+
+  Ok(add_widget(ui, image_options(egui::Image::new(source), kwargs)?, kwargs)?.0.hovered())
 }
 
-/// Control int by dragging the number.
+/// Show an image that can be dragged and return True while it is dragged. Accepts the same arguments as image.
 ///
 /// Example::
 ///
-///     data = Int(5) 
-///     # inside update_func 
-///     drag_int(data, 0, 50, 1)
+///     if image_dragged("file://assets/handle.svg", fit_to_exact_size=(32, 32)):
+///         label("dragging")
+///
+///     # use the sense argument of image to get the drag distance
+///     dx, dy = image("file://assets/handle.svg", sense="drag").drag_delta()
 #[pyfunction]
-unsafe fn drag_int(value: &mut Int, min: i32, max: i32, speed: i32) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
- 
-  ui.add(egui::DragValue::new(&mut value.value).speed(speed).range(min..=max));
-  Ok(())
+#[pyo3(signature = (source, **kwargs))]
+unsafe fn image_dragged(source: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<bool> {
+  let ui = current_ui()?;
+
+  let img = image_options(egui::Image::new(source).sense(egui::Sense::drag()), kwargs)?;
+  Ok(add_widget(ui, img, kwargs)?.0.dragged())
 }
 
-/// A clickable hyperlink
-/// 
+/// Show an image from in-memory bytes, e.g. a png downloaded at runtime. Accepts the same arguments as image.
+///
+/// The bytes are loaded once per uri. To show other bytes under the same uri, call ctx.forget_image(uri) first.
+///
 /// Example::
 ///
-///     hyperlink("https://github.com/emilk/egui")
+///     data = requests.get("https://picsum.photos/480").content
+///     # inside update_func
+///     image_from_bytes("bytes://picsum.jpg", data, max_width=200)
 #[pyfunction]
-unsafe fn hyperlink(url: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.hyperlink(url);
-  Ok(())
+#[pyo3(signature = (uri, data, **kwargs))]
+unsafe fn image_from_bytes(
+  uri: String,
+  data: &Bound<'_, PyBytes>,
+  kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  // avoid copying the bytes every frame
+  let loaded = egui::load::BytesLoader::load(ui.ctx().loaders().include.as_ref(), ui.ctx(), &uri).is_ok();
+  if !loaded {
+    ui.ctx().include_bytes(uri.clone(), data.as_bytes().to_vec());
+  }
+
+  add_widget(ui, image_options(egui::Image::new(uri), kwargs)?, kwargs)
 }
 
-/// A clickable hyperlink with label
-/// 
+/// Show a uint8 numpy array with (H, W, 3) or (H, W, 4) shape or a PIL image. Accepts the same arguments as image.
+///
+/// The image is uploaded to the GPU every frame, so it can change from frame to frame. For images that rarely change use load_texture.
+///
 /// Example::
 ///
-///     hyperlink_to("egui on GitHub", "https://www.github.com/emilk/egui/")
+///     frame = camera.read()
+///     # inside update_func
+///     image_from_array("camera", frame, max_width=640)
 #[pyfunction]
-unsafe fn hyperlink_to(label: &str, url: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.hyperlink_to(label, url);
-  Ok(())
+#[pyo3(signature = (id, array, **kwargs))]
+unsafe fn image_from_array(
+  id: &str,
+  array: &Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let image = extract_color_image(array)?;
+  let texture_id = egui::Id::new(id).with("pyegui_texture");
+
+  let texture = match ui.data(|d| d.get_temp::<egui::TextureHandle>(texture_id)) {
+    Some(mut texture) => {
+      texture.set(image, egui::TextureOptions::default());
+      texture
+    },
+    None => {
+      let texture = ui.ctx().load_texture(id, image, egui::TextureOptions::default());
+      ui.data_mut(|d| d.insert_temp(texture_id, texture.clone()));
+      texture
+    }
+  };
+
+  add_widget(ui, image_options(egui::Image::new(&texture), kwargs)?, kwargs)
 }
 
+// textures of the figures by their id and whether they were shown during this pass
+#[derive(Clone, Default)]
+struct FigureTextures(HashMap<u64, (egui::TextureHandle, bool)>);
 
-/// Clickable text, that looks like a hyperlink.
-/// To link to a web page, use hyperlink or hyperlink_to.
-/// 
-/// Example::
-///
-///     if link_clicked("egui on GitHub"):
-///       print("clicked on a fake link")
-#[pyfunction]
-unsafe fn link_clicked(label: &str) -> PyResult<bool> {
-  let ui = current_ui(&UI)?;
-  
-  Ok(ui.link(label).clicked())
+fn figure_id(fig: &Bound<'_, PyAny>) -> PyResult<u64> {
+  if let Ok(id) = fig.getattr("_pyegui_id") {
+    return id.extract();
+  }
+
+  let id = NEXT_FIGURE_ID.fetch_add(1, Ordering::Relaxed);
+  fig.setattr("_pyegui_id", id)?;
+  Ok(id)
 }
 
-/// Show a checkbox.
-/// 
-/// Example::
-///
-///     data = Bool(false)
-///     # inside update_func
-///     checkbox(data, "check me")
-#[pyfunction]
-unsafe fn checkbox(checked: &mut Bool, text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.checkbox(&mut checked.value, text);
-  Ok(())
+// frees the textures of the figures that were not shown during the pass
+fn evict_figure_textures(ctx: &egui::Context) {
+  ctx.data_mut(|d| {
+    d.get_temp_mut_or_default::<FigureTextures>(egui::Id::new(FIGURES_KEY)).0
+      .retain(|_, (_, shown)| std::mem::replace(shown, false));
+  });
 }
 
-/// Acts like a checkbox, but looks like a selectable label.
-/// 
+/// Show a Matplotlib figure. Accepts the same arguments as image.
+///
+/// The figure is rendered again only when Matplotlib marks it as stale, e.g. after plotting new data.
+/// The rendered figure is freed when the figure is not shown for a frame.
+///
 /// Example::
 ///
-///     data = Bool(false)
+///     fig, ax = plt.subplots()
+///     ax.plot([1, 2, 3], [4, 1, 3])
 ///     # inside update_func
-///     toggle_value(data, "check me")
+///     matplotlib_figure(fig, max_width=400)
 #[pyfunction]
-unsafe fn toggle_value(selected: &mut Bool, text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.toggle_value(&mut selected.value, text);
-  Ok(())
-}
+#[pyo3(signature = (fig, **kwargs))]
+unsafe fn matplotlib_figure(
+  fig: &Bound<'_, PyAny>,
+  kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let id = figure_id(fig)?;
+  let mut first_figure = false;
+
+  let cached = ui.data_mut(|d| {
+    let figures = d.get_temp_mut_or_insert_with(egui::Id::new(FIGURES_KEY), || {
+      first_figure = true;
+      FigureTextures::default()
+    });
 
+    figures.0.get_mut(&id).map(|(texture, shown)| {
+      *shown = true;
+      texture.clone()
+    })
+  });
+  if first_figure {
+    ui.ctx().on_end_pass(FIGURES_KEY, Arc::new(evict_figure_textures));
+  }
 
-/// Show a radio button. It is selected if current_value == selected_value. If clicked, selected_value is assigned to current_value.
-/// 
+  let texture = match cached {
+    Some(texture) if !fig.getattr("stale")?.is_truthy()? => texture,
+    cached => {
+      let canvas = fig.getattr("canvas")?;
+      canvas.call_method0("draw")?;
+      let image = extract_color_image(&canvas.call_method0("buffer_rgba")?)?;
+
+      match cached {
+        Some(mut texture) => {
+          texture.set(image, egui::TextureOptions::default());
+          texture
+        },
+        None => {
+          let texture = ui.ctx().load_texture("matplotlib_figure", image, egui::TextureOptions::default());
+          ui.data_mut(|d| {
+            d.get_temp_mut_or_default::<FigureTextures>(egui::Id::new(FIGURES_KEY)).0.insert(id, (texture.clone(), true));
+          });
+          texture
+        }
+      }
+    }
+  };
+
+  add_widget(ui, image_options(egui::Image::new(&texture), kwargs)?, kwargs)
+}
+
+/// Upload an image to the GPU and return its TextureHandle. Show it with textured_image.
+///
+/// Args:
+///     name (str): name of the texture, used for debugging
+///
+///     image (numpy.ndarray | PIL.Image.Image): uint8 array with (H, W, 3) or (H, W, 4) shape or a PIL image
+///
+///     filter (str): "linear" smooths the image when it is scaled, "nearest" keeps it pixelated. Default is "linear"
+///
 /// Example::
 ///
-///     RED = 0
-///     GREEN = 1
-///     BLUE = 2
-///     
-///     c = Int(RED)
-///     
-///     radio_value(c, RED, "red")
-///     radio_value(c, GREEN, "green")
-///     radio_value(c, BLUE, "blue")
+///     sprite = load_texture("sprite", PIL.Image.open("sprite.png"), "nearest")
 #[pyfunction]
-unsafe fn radio_value(current_value: &mut Int, alternative: i32, text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.radio_value(&mut current_value.value, alternative, text);
-  Ok(())
-}
+#[pyo3(signature = (name, image, filter = "linear"))]
+unsafe fn load_texture(name: &str, image: &Bound<'_, PyAny>, filter: &str) -> PyResult<TextureHandle> {
+  let ui = current_ui()?;
 
+  Ok(TextureHandle(ui.ctx().load_texture(name, extract_color_image(image)?, texture_options(filter)?)))
+}
 
-/// Show selectable text. It is selected if current_value == selected_value. If clicked, selected_value is assigned to current_value.
-/// 
+/// Show a texture loaded with load_texture. Accepts the same arguments as image.
+///
 /// Example::
 ///
-///     RED = 0
-///     GREEN = 1
-///     BLUE = 2
-///     
-///     c = Int(RED)
-///     
-///     selectable_value(c, RED, "red")
-///     selectable_value(c, GREEN, "green")
-///     selectable_value(c, BLUE, "blue")
+///     textured_image(sprite, fit_to_exact_size=(64, 64))
 #[pyfunction]
-unsafe fn selectable_value(current_value: &mut Int, alternative: i32, text: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.selectable_value(&mut current_value.value, alternative, text);
-  Ok(())
+#[pyo3(signature = (texture, **kwargs))]
+unsafe fn textured_image(texture: &TextureHandle, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_widget(ui, image_options(egui::Image::new(&texture.0), kwargs)?, kwargs)
 }
 
-/// Shows a combo box with values defined in "alternatives" and their corresponding names
-/// defined in "names"
-/// 
-/// Example::
+/// A button showing only an image. Accepts the sizing arguments of image.
 ///
-///     RED = 0
-///     GREEN = 1
-///     BLUE = 2
+/// Args:
+///     frame (bool): whether to draw the button frame. Default is True
 ///
-///     data = Int(RED)
+///     selected (bool): show the button as selected
 ///
-///     def update_func(a):
-///         combo_box(data, [RED, GREEN, BLUE], ["red", "green", "blue"], "choose your fate")
+///     tint (Color | str | tuple[int, int, int]): multiply the image with this color
+///
+///     corner_radius (float): round the corners of the button
+///
+/// Example::
+///
+///     if image_button("file://icons/save.svg", fit_to_exact_size=(24, 24), frame=False).clicked():
+///         save()
 #[pyfunction]
-unsafe fn combo_box(current_value: &mut Int, alternatives: Vec<i32>, names: Vec<String>, label: &str) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (source, **kwargs))]
+unsafe fn image_button(source: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
 
-  egui::ComboBox::from_label(label)
-    .selected_text(names.get(current_value.value.try_into().unwrap_or(0)).unwrap_or(&"Unknown".to_string()))
-    .show_ui(ui, |ui| {
-      for i in 0..alternatives.len() {
-        ui.selectable_value(
-          &mut current_value.value, 
-          alternatives[i], 
-          names.get(i).unwrap_or(&"Unknown".to_string())
-        );
-      }
-    }
-  );
-  Ok(())
+  let img = image_options(egui::Image::new(source), kwargs)?;
+  add_widget(ui, image_button_options(egui::ImageButton::new(img), kwargs)?, kwargs)
+}
+
+/// An image button that toggles selected.value when clicked and is shown as selected while it is True.
+/// Accepts the same arguments as image_button.
+///
+/// Example::
+///
+///     bold = Bool(False)
+///     # inside update_func
+///     with horizontal():
+///         image_toggle_value(bold, "file://icons/bold.svg", fit_to_exact_size=(24, 24))
+///         image_toggle_value(italic, "file://icons/italic.svg", fit_to_exact_size=(24, 24))
+#[pyfunction]
+#[pyo3(signature = (selected, source, **kwargs))]
+unsafe fn image_toggle_value(selected: &mut Bool, source: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  let img = image_options(egui::Image::new(source), kwargs)?;
+  let button = image_button_options(egui::ImageButton::new(img), kwargs)?.selected(selected.value);
+
+  let mut r = add_widget(ui, button, kwargs)?;
+  if r.0.clicked() {
+    selected.value = !selected.value;
+    r.0.mark_changed();
+  }
+
+  Ok(r)
 }
 
-/// A simple progress bar.
-/// value in the [0, 1] range, where 1 means “completed”.
+/// Creates a button with an image to the left of the text 
 ///
 /// Example::
 ///
-///     progress(0.5)
+///     if image_and_text_clicked("https://picsum.photos/480", "click me"):
+///       print("clicked")
 #[pyfunction]
-unsafe fn progress(value: f32) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+unsafe fn image_and_text_clicked(source: &str, text: &str) -> PyResult<bool> {
+  let ui = current_ui()?;
   
-  ui.add(egui::widgets::ProgressBar::new(value).show_percentage());
-  Ok(())
+  Ok(ui.add(egui::Button::image_and_text(source, text)).clicked())
 }
 
-
-/// A spinner widget used to indicate loading.
+/// Creates a button with an image to the left of the text and returns its Response
 ///
 /// Example::
 ///
-///     spinner()
+///     if image_and_text("https://picsum.photos/480", "hover me").hovered():
+///       label("hovered")
 #[pyfunction]
-unsafe fn spinner() -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  ui.spinner();
-  Ok(())
+#[pyo3(signature = (source, text, **kwargs))]
+unsafe fn image_and_text(source: &str, text: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_widget(ui, button_options(egui::Button::image_and_text(source, text), kwargs)?, kwargs)
 }
 
-/// Shows a button with the given color. If the user clicks the button, a full color picker is shown.
-/// 
+/// Returns a Painter that draws on the current Ui.
+///
 /// Example::
 ///
-///     color = RGB(69, 69, 69)
-///     # inside udpate_func
-///     color_edit_button_rgb(color)
-///     heading(f"r:{color.r} g:{color.g} b:{color.b}")
+///     r = button("target").rect
+///     painter().circle_stroke(r.center, 20.0, width=2.0, color="#ff0000")
 #[pyfunction]
-unsafe fn color_edit_button_rgb(rgb: &mut RGB) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-
-  let mut tmp: [f32; 3] = [rgb.r, rgb.g, rgb.b];
+unsafe fn painter() -> PyResult<Painter> {
+  let ui = current_ui()?;
 
-  ui.color_edit_button_rgb(&mut tmp);
+  Ok(Painter(ui.painter().clone()))
+}
 
-  rgb.r = tmp[0];
-  rgb.g = tmp[1];
-  rgb.b = tmp[2];
+/// Reserve space for a custom widget. Returns its Response and a Painter clipped to the allocated rect.
+///
+/// Args:
+///     size (tuple[float, float]): desired width and height
+///
+///     sense (str): which interactions the widget reacts to: "hover", "click", "drag" or "click_and_drag". Default is "click"
+///
+/// Example::
+///
+///     def toggle_switch(on: Bool):
+///         r, p = allocate_response((40, 20))
+///         if r.clicked():
+///             on.value = not on.value
+///         x0, y0 = r.rect.min
+///         p.rect_filled(r.rect, "#4caf50" if on.value else "#808080", corner_radius=10)
+///         p.circle_filled((x0 + (30 if on.value else 10), y0 + 10), 8, "#ffffff")
+///         return r
+#[pyfunction]
+#[pyo3(signature = (size, sense = "click"))]
+unsafe fn allocate_response(size: (f32, f32), sense: &str) -> PyResult<(Response, Painter)> {
+  let ui = current_ui()?;
 
-  Ok(())
+  let (r, p) = ui.allocate_painter(size.into(), extract_sense(sense)?);
+  Ok((Response(r), Painter(p)))
 }
 
-
-/// Show an image available at the given uri.
+/// Make the content draggable. While it is dragged, payload is available to drop zones and
+/// Response.dnd_hover_payload / Response.dnd_release_payload of other widgets.
+///
+/// Args:
+///     id (str): unique id of the drag source
+///
+///     payload (Any): any object to pass to the drop target
+///
+///     update_fun (Callable[[], None]): your function that draws the content
 ///
 /// Example::
 ///
-///     image("https://picsum.photos/480")
-///     image("file://assets/ferris.png", max_height = 50, max_width = 50)
+///     for task in tasks:
+///         dnd_drag_source(task.id, task, lambda: label(task.title))
 #[pyfunction]
-#[pyo3(signature = (source, **kwargs))]
-unsafe fn image(
-  source: &str, 
-  kwargs: Option<&Bound<'_, PyDict>>,
-) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
-  
-  let mut img = egui::Image::new(source);
+unsafe fn dnd_drag_source(id: &str, payload: Py<PyAny>, update_fun: Bound<'_, PyAny>) -> PyResult<Response> {
+  let ui = current_ui()?;
 
-  if let Some(kwargs) = kwargs {
-    if let Some(height) = kwargs.get_item("max_height")? {
-      img = img.max_height(height.downcast::<PyInt>()?.extract()?);
-    }
-    if let Some(width) = kwargs.get_item("max_width")? {
-      img = img.max_width(width.downcast::<PyInt>()?.extract()?);
-    }
-  }
-  ui.add(img);
-  Ok(())
+  let r = ui.dnd_drag_source(egui::Id::new(id), DragPayload(payload), |ui| run_nested_update_func(ui, update_fun));
+  r.inner.map(|_| Response(r.response))
 }
 
-/// Creates a button with an image to the left of the text 
+/// A frame that highlights itself while something is dragged and returns the payload dropped onto it this frame, or None.
 ///
 /// Example::
 ///
-///     if image_and_text_clicked("https://picsum.photos/480", "click me"):
-///       print("clicked")
+///     def done_column():
+///         for task in done:
+///             label(task.title)
+///
+///     task = dnd_drop_zone(done_column)
+///     if task is not None:
+///         todo.remove(task)
+///         done.append(task)
 #[pyfunction]
-unsafe fn image_and_text_clicked(source: &str, text: &str) -> PyResult<bool> {
-  let ui = current_ui(&UI)?;
-  
-  Ok(ui.add(egui::Button::image_and_text(source, text)).clicked())
+unsafe fn dnd_drop_zone(update_fun: Bound<'_, PyAny>) -> PyResult<Option<Py<PyAny>>> {
+  let ui = current_ui()?;
+
+  let (r, payload) = ui.dnd_drop_zone::<DragPayload, _>(
+    egui::Frame::default().inner_margin(4.0),
+    |ui| run_nested_update_func(ui, update_fun)
+  );
+  r.inner?;
+
+  Ok(payload.map(|p| Python::with_gil(|py| p.0.clone_ref(py))))
 }
 
 /// A visual separator. A horizontal or vertical line on layout.
 ///
+/// Args:
+///     spacing (float): space taken by the separator across the line. Default is 6
+///
+///     grow (float): make the line longer than the available space by this much on both ends
+///
+///     shrink (float): make the line shorter than the available space by this much on both ends
+///
 /// Example::
 ///
 ///     separator()
+///     separator(spacing=20, grow=8)
 #[pyfunction]
-unsafe fn separator() -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (**kwargs))]
+unsafe fn separator(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
   
-  ui.separator();
-  Ok(())
+  add_widget(ui, separator_options(egui::Separator::default(), kwargs)?, kwargs)
+}
+
+/// A vertical line, whatever the layout is. It divides e.g. groups of buttons in a toolbar.
+///
+/// It accepts the same arguments as separator.
+///
+/// Example::
+///
+///     with horizontal():
+///       button("Cut")
+///       button("Copy")
+///       separator_vertical()
+///       button("Undo")
+///       button("Redo")
+#[pyfunction]
+#[pyo3(signature = (**kwargs))]
+unsafe fn separator_vertical(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  add_widget(ui, separator_options(egui::Separator::default().vertical(), kwargs)?, kwargs)
 }
 
 
@@ -1079,7 +7513,7 @@ unsafe fn separator() -> PyResult<()> {
 ///     heading("this will not be visible")
 #[pyfunction]
 unsafe fn set_invisible() -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+  let ui = current_ui()?;
   
   ui.set_invisible();
   Ok(())
@@ -1098,7 +7532,7 @@ unsafe fn set_invisible() -> PyResult<()> {
 ///       pass
 #[pyfunction]
 unsafe fn disable() -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+  let ui = current_ui()?;
   
   ui.disable();
   Ok(())
@@ -1115,7 +7549,7 @@ unsafe fn disable() -> PyResult<()> {
 #[pyfunction]
 unsafe fn add_enabled(enabled: bool, update_fun: Bound<'_, PyAny>) -> PyResult<()> {
 
-  current_ui(&UI)?.add_enabled_ui(enabled, |ui| run_nested_update_func(ui, update_fun)).inner
+  current_ui()?.add_enabled_ui(enabled, |ui| run_nested_update_func(ui, update_fun)).inner
 }
 
 /// Make the widget in this Ui semi-transparent.
@@ -1126,7 +7560,7 @@ unsafe fn add_enabled(enabled: bool, update_fun: Bound<'_, PyAny>) -> PyResult<(
 ///     set_opacity(0.5)
 #[pyfunction]
 unsafe fn set_opacity(opacity: f32) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+  let ui = current_ui()?;
   
   ui.set_opacity(opacity);
   Ok(())
@@ -1141,11 +7575,117 @@ unsafe fn set_opacity(opacity: f32) -> PyResult<()> {
 ///     # inside update_func
 ///     date_picker_button(date)
 #[pyfunction]
-unsafe fn date_picker_button(selection: &mut Date) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+#[pyo3(signature = (selection, **kwargs))]
+unsafe fn date_picker_button(selection: &mut Date, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
   
-  ui.add(egui_extras::DatePickerButton::new(&mut selection.value));
-  Ok(())
+  add_widget(ui, egui_extras::DatePickerButton::new(&mut selection.value), kwargs)
+}
+
+/// Shows hour and minute fields of the time.
+///
+/// Args:
+///     show_seconds (bool): show a field for seconds too
+///
+/// Example::
+///
+///     alarm = Time(datetime.time(7, 0))
+///     # inside update_func
+///     time_picker(alarm)
+#[pyfunction]
+#[pyo3(signature = (time, **kwargs))]
+unsafe fn time_picker(time: &mut Time, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+
+  Ok(Response(time_fields(ui, &mut time.value, show_seconds(kwargs)?)))
+}
+
+/// Shows a date picker button and hour and minute fields.
+///
+/// Args:
+///     show_seconds (bool): show a field for seconds too
+///
+///     id_salt (str): id of the date picker. Use it when there are several pickers in the same ui
+///
+/// Example::
+///
+///     meeting = DateTime(datetime.datetime.now())
+///     # inside update_func
+///     datetime_picker(meeting)
+#[pyfunction]
+#[pyo3(signature = (datetime, **kwargs))]
+unsafe fn datetime_picker(datetime: &mut DateTime, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let seconds = show_seconds(kwargs)?;
+  let mut id_salt = "datetime".to_string();
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(salt) = kwargs.get_item("id_salt")? {
+      id_salt = salt.downcast::<PyString>()?.extract()?;
+    }
+  }
+
+  let mut date = datetime.value.date();
+  let mut time = datetime.value.time();
+
+  let r = ui.horizontal(|ui| {
+    let date_r = ui.add(egui_extras::DatePickerButton::new(&mut date).id_salt(&id_salt));
+    date_r | time_fields(ui, &mut time, seconds)
+  });
+  if r.inner.changed() {
+    datetime.value = date.and_time(time);
+  }
+
+  Ok(Response(r.inner))
+}
+
+/// Shows two date picker buttons for the start and the end of a range. The range is kept valid:
+/// if start is picked after end, end is moved to start and vice versa.
+///
+/// Args:
+///     id_salt (str): id of the picker. Use it when there are several date range pickers in the same ui
+///
+/// Example::
+///
+///     start = Date(datetime.date(2025, 1, 1))
+///     end = Date(datetime.date.today())
+///     # inside update_func
+///     if date_range_picker(start, end).changed():
+///       load_report(start.value, end.value)
+#[pyfunction]
+#[pyo3(signature = (start, end, **kwargs))]
+unsafe fn date_range_picker(start: &mut Date, end: &mut Date, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Response> {
+  let ui = current_ui()?;
+  let mut id_salt = "date_range".to_string();
+
+  if let Some(kwargs) = kwargs {
+
+    if let Some(salt) = kwargs.get_item("id_salt")? {
+      id_salt = salt.downcast::<PyString>()?.extract()?;
+    }
+  }
+
+  let start_salt = format!("{}_start", id_salt);
+  let end_salt = format!("{}_end", id_salt);
+
+  let r = ui.horizontal(|ui| {
+    let start_r = ui.add(egui_extras::DatePickerButton::new(&mut start.value).id_salt(&start_salt));
+    ui.label("–");
+    let end_r = ui.add(egui_extras::DatePickerButton::new(&mut end.value).id_salt(&end_salt));
+
+    if start.value > end.value {
+      if start_r.changed() {
+        end.value = start.value;
+      } else {
+        start.value = end.value;
+      }
+    }
+
+    start_r | end_r
+  });
+
+  Ok(Response(r.inner))
 }
 
 /// Add extra space before the next widget.
@@ -1157,12 +7697,357 @@ unsafe fn date_picker_button(selection: &mut Date) -> PyResult<()> {
 ///     heading("I'm so spaced now")
 #[pyfunction]
 unsafe fn add_space(amount: f32) -> PyResult<()> {
-  let ui = current_ui(&UI)?;
+  let ui = current_ui()?;
   
   ui.add_space(amount);
   Ok(())
 }
 
+/// Set the width of the current Ui, e.g. inside a container, so that widgets don't grow wider.
+///
+/// Example::
+///
+///     with group():
+///       set_width(200)
+///       label("This text wraps at 200 points")
+#[pyfunction]
+unsafe fn set_width(width: f32) -> PyResult<()> {
+  current_ui()?.set_width(width);
+  Ok(())
+}
+
+/// Set the height of the current Ui
+#[pyfunction]
+unsafe fn set_height(height: f32) -> PyResult<()> {
+  current_ui()?.set_height(height);
+  Ok(())
+}
+
+/// Set the minimal width of the current Ui. It can still grow wider
+#[pyfunction]
+unsafe fn set_min_width(width: f32) -> PyResult<()> {
+  current_ui()?.set_min_width(width);
+  Ok(())
+}
+
+/// Set the minimal height of the current Ui. It can still grow higher
+#[pyfunction]
+unsafe fn set_min_height(height: f32) -> PyResult<()> {
+  current_ui()?.set_min_height(height);
+  Ok(())
+}
+
+/// Set the maximal width of the current Ui. Text wraps and widgets shrink to fit it
+#[pyfunction]
+unsafe fn set_max_width(width: f32) -> PyResult<()> {
+  current_ui()?.set_max_width(width);
+  Ok(())
+}
+
+/// Set the maximal height of the current Ui
+#[pyfunction]
+unsafe fn set_max_height(height: f32) -> PyResult<()> {
+  current_ui()?.set_max_height(height);
+  Ok(())
+}
+
+/// Set the minimal width and height of the current Ui
+///
+/// Example::
+///
+///     with frame(fill=Color.DARK_GRAY):
+///       set_min_size((300, 100))
+///       label("At least 300x100")
+#[pyfunction]
+unsafe fn set_min_size(size: (f32, f32)) -> PyResult<()> {
+  current_ui()?.set_min_size(size.into());
+  Ok(())
+}
+
+/// Set the maximal width and height of the current Ui
+#[pyfunction]
+unsafe fn set_max_size(size: (f32, f32)) -> PyResult<()> {
+  current_ui()?.set_max_size(size.into());
+  Ok(())
+}
+
+/// Width of the space left in the current Ui for widgets
+///
+/// Example::
+///
+///     # how many 120 points wide thumbnails fit in a row
+///     per_row = max(1, int(available_width() // 120))
+///     for row in range(0, len(thumbnails), per_row):
+///       with horizontal():
+///         for thumb in thumbnails[row:row + per_row]:
+///           image(thumb, max_size=(112, 112))
+#[pyfunction]
+unsafe fn available_width() -> PyResult<f32> {
+  Ok(current_ui()?.available_width())
+}
+
+/// Height of the space left in the current Ui for widgets
+#[pyfunction]
+unsafe fn available_height() -> PyResult<f32> {
+  Ok(current_ui()?.available_height())
+}
+
+/// Width and height of the space left in the current Ui for widgets
+#[pyfunction]
+unsafe fn available_size() -> PyResult<(f32, f32)> {
+  let size = current_ui()?.available_size();
+  Ok((size.x, size.y))
+}
+
+// state of an animation of Context.tween, kept in the context data
+#[derive(Clone, Copy)]
+struct Tween {
+  from: f32,
+  to: f32,
+  started_at: f64,
+}
+
+// easing of Context.tween, a name is checked even when nothing is animated
+enum Easing<'a, 'py> {
+  Named(fn(f32) -> f32),
+  Custom(&'a Bound<'py, PyAny>),
+}
+
+impl<'a, 'py> Easing<'a, 'py> {
+  fn extract(easing: Option<&'a Bound<'py, PyAny>>) -> PyResult<Self> {
+    match easing {
+      Some(f) if f.is_callable() => Ok(Easing::Custom(f)),
+      Some(name) => Ok(Easing::Named(easing_fn(name.downcast::<PyString>()?.to_str()?)?)),
+      None => Ok(Easing::Named(egui::emath::easing::cubic_in_out)),
+    }
+  }
+}
+
+impl Tween {
+  fn value(&self, now: f64, duration: f32, easing: &Easing<'_, '_>) -> PyResult<f32> {
+    let progress = if duration > 0.0 {
+      ((now - self.started_at) as f32 / duration).clamp(0.0, 1.0)
+    } else {
+      1.0
+    };
+
+    if progress >= 1.0 {
+      return Ok(self.to);
+    }
+
+    let eased = match easing {
+      Easing::Named(f) => f(progress),
+      Easing::Custom(f) => f.call1((progress,))?.extract::<f32>()?,
+    };
+
+    Ok(self.from + (self.to - self.from) * eased)
+  }
+}
+
+fn easing_fn(name: &str) -> PyResult<fn(f32) -> f32> {
+  use egui::emath::easing::*;
+
+  Ok(match name {
+    "linear" => linear,
+    "quadratic_in" => quadratic_in,
+    "quadratic_out" => quadratic_out,
+    "quadratic_in_out" => quadratic_in_out,
+    "cubic_in" => cubic_in,
+    "cubic_out" => cubic_out,
+    "cubic_in_out" => cubic_in_out,
+    "sin_in" => sin_in,
+    "sin_out" => sin_out,
+    "sin_in_out" => sin_in_out,
+    "circular_in" => circular_in,
+    "circular_out" => circular_out,
+    "circular_in_out" => circular_in_out,
+    "exponential_in" => exponential_in,
+    "exponential_out" => exponential_out,
+    "exponential_in_out" => exponential_in_out,
+    "back_in" => back_in,
+    "back_out" => back_out,
+    "back_in_out" => back_in_out,
+    "bounce_in" => bounce_in,
+    "bounce_out" => bounce_out,
+    "bounce_in_out" => bounce_in_out,
+    other => return Err(PyValueError::new_err(format!("Unknown easing '{}'", other)))
+  })
+}
+
+// testing
+
+/// Runs update_fun without a window, so that the UI can be tested, e.g. with pytest.
+///
+/// Widgets are found by their label. Exceptions raised by update_fun are raised by the Harness methods.
+///
+/// Args:
+///     update_fun (Callable[[Context], None]): your function that draws UI
+///
+///     width (float): width of the screen. Default is 800
+///
+///     height (float): height of the screen. Default is 600
+///
+/// Example::
+///
+///     from pyegui.testing import Harness
+///
+///     def test_counter():
+///         count = Int(0)
+///
+///         def update_fun(ctx):
+///             label(f"Count: {count.value}")
+///             if button_clicked("Increment"):
+///                 count.value += 1
+///
+///         h = Harness(update_fun)
+///         h.click("Increment")
+///         assert count.value == 1
+///         assert h.has_label("Count: 1")
+#[cfg(feature = "testing")]
+#[pyclass(unsendable, module = "pyegui.testing")]
+struct Harness(egui_kittest::Harness<'static, Option<PyErr>>);
+
+#[cfg(feature = "testing")]
+#[pymethods]
+impl Harness {
+    #[new]
+    #[pyo3(signature = (update_fun, width = 800.0, height = 600.0))]
+    fn new(update_fun: Py<PyAny>, width: f32, height: f32) -> PyResult<Self> {
+        let app = move |ctx: &egui::Context, error: &mut Option<PyErr>| {
+          egui::CentralPanel::default().show(ctx, |ui| {
+            let _guard = push_ui(ui);
+
+            // the first exception is raised, the next frames are skipped
+            if error.is_some() {
+              return;
+            }
+
+            Python::with_gil(|py| {
+              if let Err(err) = update_fun.call1(py, (Context(ctx.clone()),)) {
+                *error = Some(err);
+              }
+            });
+          });
+        };
+
+        let harness = egui_kittest::Harness::builder()
+          .with_size(egui::vec2(width, height))
+          .build_state(app, None);
+
+        let mut harness = Harness(harness);
+        harness.raise_error()?;
+
+        Ok(harness)
+    }
+
+    /// Run frames until the UI stops changing, e.g. until animations end
+    fn run(&mut self) -> PyResult<()> {
+        let result = self.0.try_run();
+        self.raise_error()?;
+
+        result
+          .map(|_| ())
+          .map_err(|err| PyRuntimeError::new_err(err.to_string()))
+    }
+
+    /// Run the given number of frames
+    #[pyo3(signature = (frames = 1))]
+    fn step(&mut self, frames: usize) -> PyResult<()> {
+        self.0.run_steps(frames);
+        self.raise_error()
+    }
+
+    /// True if there is a widget with this label
+    fn has_label(&self, label: &str) -> bool {
+        self.0.query_all_by_label(label).next().is_some()
+    }
+
+    /// Click the widget with this label and run frames until the UI stops changing
+    fn click(&mut self, label: &str) -> PyResult<()> {
+        find_by_label(&self.0, label)?.click();
+        self.run()
+    }
+
+    /// Type text into the text field with this label and run frames until the UI stops changing.
+    /// Without a label the text is typed into the only text field.
+    #[pyo3(signature = (text, label = None))]
+    fn type_text(&mut self, text: &str, label: Option<&str>) -> PyResult<()> {
+        match label {
+          Some(label) => find_by_label(&self.0, label)?.type_text(text),
+          None => {
+            let mut inputs = self.0.query_all_by_role(egui::accesskit::Role::TextInput)
+              .chain(self.0.query_all_by_role(egui::accesskit::Role::MultilineTextInput))
+              .chain(self.0.query_all_by_role(egui::accesskit::Role::PasswordInput));
+
+            match (inputs.next(), inputs.next()) {
+              (Some(input), None) => input.type_text(text),
+              (None, _) => return Err(PyLookupError::new_err("There is no text field")),
+              (Some(_), Some(_)) => return Err(PyLookupError::new_err("There are several text fields, pass the label of one of them")),
+            }
+          }
+        }
+
+        self.run()
+    }
+
+    /// Press and release the key, then run frames until the UI stops changing
+    fn press_key(&mut self, key: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.0.press_key(extract_key(key)?);
+        self.run()
+    }
+
+    /// Render the UI to `{output_path}/{name}.new.png` and compare it with the baseline `{output_path}/{name}.png`.
+    ///
+    /// If the images differ, AssertionError is raised and the difference is saved to `{output_path}/{name}.diff.png`.
+    /// Run the tests with the UPDATE_SNAPSHOTS=1 environment variable to create or update the baselines.
+    ///
+    /// Args:
+    ///     name (str): name of the snapshot
+    ///
+    ///     threshold (float): how much a pixel may differ before it counts as changed. Default is 0.6
+    ///
+    ///     output_path (str): directory of the snapshots. Default is "tests/snapshots"
+    ///
+    /// Example::
+    ///
+    ///     def test_look():
+    ///         h = Harness(update_fun, width=400, height=300)
+    ///         h.snapshot("main_window")
+    #[pyo3(signature = (name, threshold = 0.6, output_path = "tests/snapshots"))]
+    fn snapshot(&mut self, name: &str, threshold: f32, output_path: &str) -> PyResult<()> {
+        let options = egui_kittest::SnapshotOptions::new()
+          .threshold(threshold)
+          .output_path(output_path);
+
+        match self.0.try_snapshot_options(name, &options) {
+          Ok(()) => Ok(()),
+          Err(err @ egui_kittest::SnapshotError::RenderError { .. }) => Err(PyRuntimeError::new_err(err.to_string())),
+          Err(err) => Err(PyAssertionError::new_err(err.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl Harness {
+    fn raise_error(&mut self) -> PyResult<()> {
+        match self.0.state_mut().take() {
+          Some(err) => Err(err),
+          None => Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+fn find_by_label<'a>(harness: &'a egui_kittest::Harness<'static, Option<PyErr>>, label: &'a str) -> PyResult<egui_kittest::kittest::Node<'a>> {
+  let mut nodes = harness.query_all_by_label(label);
+
+  match (nodes.next(), nodes.next()) {
+    (Some(node), None) => Ok(node),
+    (None, _) => Err(PyLookupError::new_err(format!("There is no widget labelled '{}'", label))),
+    (Some(_), Some(_)) => Err(PyLookupError::new_err(format!("There are several widgets labelled '{}'", label))),
+  }
+}
+
 #[pymodule]
 fn pyegui(m: &Bound<'_, PyModule>) -> PyResult<()> {
   pyo3_log::init();
@@ -1171,31 +8056,138 @@ fn pyegui(m: &Bound<'_, PyModule>) -> PyResult<()> {
   m.add_class::<Bool>()?;
   m.add_class::<Int>()?;
   m.add_class::<Float>()?;
+  m.add_class::<SharedStr>()?;
+  m.add_class::<SharedBool>()?;
+  m.add_class::<SharedInt>()?;
+  m.add_class::<SharedFloat>()?;
+  m.add_class::<Channel>()?;
+  m.add_class::<Color>()?;
+  m.add_class::<Style>()?;
   m.add_class::<RGB>()?;
+  m.add_class::<RGBA>()?;
+  m.add_class::<HSVA>()?;
   m.add_class::<Date>()?;
+  m.add_class::<Time>()?;
+  m.add_class::<DateTime>()?;
   m.add_class::<Context>()?;
+  m.add_class::<ViewportCommand>()?;
+  m.add_class::<Layout>()?;
+  m.add_class::<Rect>()?;
+  m.add_class::<Response>()?;
+  m.add_class::<Container>()?;
+  m.add_class::<TextureHandle>()?;
+  m.add_class::<Painter>()?;
+  m.add_class::<Key>()?;
+  m.add_class::<RepaintHandle>()?;
+  m.add_class::<Modifiers>()?;
+  m.add_class::<DroppedFile>()?;
+  m.add_class::<HoveredFile>()?;
+  m.add_class::<Line>()?;
+  m.add_class::<Points>()?;
+  m.add_class::<BarChart>()?;
+  m.add_class::<PlotResponse>()?;
+  // named colors
+  let color = m.getattr("Color")?;
+  for (name, value) in [
+    ("TRANSPARENT", egui::Color32::TRANSPARENT),
+    ("BLACK", egui::Color32::BLACK),
+    ("DARK_GRAY", egui::Color32::DARK_GRAY),
+    ("GRAY", egui::Color32::GRAY),
+    ("LIGHT_GRAY", egui::Color32::LIGHT_GRAY),
+    ("WHITE", egui::Color32::WHITE),
+    ("BROWN", egui::Color32::BROWN),
+    ("DARK_RED", egui::Color32::DARK_RED),
+    ("RED", egui::Color32::RED),
+    ("LIGHT_RED", egui::Color32::LIGHT_RED),
+    ("CYAN", egui::Color32::CYAN),
+    ("MAGENTA", egui::Color32::MAGENTA),
+    ("YELLOW", egui::Color32::YELLOW),
+    ("ORANGE", egui::Color32::ORANGE),
+    ("LIGHT_YELLOW", egui::Color32::LIGHT_YELLOW),
+    ("KHAKI", egui::Color32::KHAKI),
+    ("DARK_GREEN", egui::Color32::DARK_GREEN),
+    ("GREEN", egui::Color32::GREEN),
+    ("LIGHT_GREEN", egui::Color32::LIGHT_GREEN),
+    ("DARK_BLUE", egui::Color32::DARK_BLUE),
+    ("BLUE", egui::Color32::BLUE),
+    ("LIGHT_BLUE", egui::Color32::LIGHT_BLUE),
+    ("PURPLE", egui::Color32::PURPLE),
+    ("GOLD", egui::Color32::GOLD),
+  ] {
+    color.setattr(name, Color(value))?;
+  }
+  // keys
+  let key = m.getattr("Key")?;
+  for value in egui::Key::ALL {
+    key.setattr(format!("{:?}", value), Key(*value))?;
+  }
+  // testing
+  #[cfg(feature = "testing")]
+  {
+    let testing = PyModule::new(m.py(), "testing")?;
+    testing.add_class::<Harness>()?;
+    m.add_submodule(&testing)?;
+    // makes `from pyegui.testing import Harness` work
+    m.py().import("sys")?.getattr("modules")?.set_item("pyegui.testing", testing)?;
+  }
   // functions
   m.add_function(wrap_pyfunction!(run_native, m)?)?;
+  m.add_function(wrap_pyfunction!(set_error_handler, m)?)?;
+  m.add_function(wrap_pyfunction!(set_accessibility, m)?)?;
+  m.add_function(wrap_pyfunction!(add_font, m)?)?;
+  m.add_function(wrap_pyfunction!(set_font_family, m)?)?;
   m.add_function(wrap_pyfunction!(heading, m)?)?;
   m.add_function(wrap_pyfunction!(monospace, m)?)?;
   m.add_function(wrap_pyfunction!(small, m)?)?;
   m.add_function(wrap_pyfunction!(strong, m)?)?;
   m.add_function(wrap_pyfunction!(weak, m)?)?;
   m.add_function(wrap_pyfunction!(label, m)?)?;
+  m.add_function(wrap_pyfunction!(colored_label, m)?)?;
   m.add_function(wrap_pyfunction!(code, m)?)?;
   m.add_function(wrap_pyfunction!(code_editor, m)?)?;
   m.add_function(wrap_pyfunction!(text_edit_singleline, m)?)?;
   m.add_function(wrap_pyfunction!(text_edit_multiline, m)?)?;
   m.add_function(wrap_pyfunction!(button_clicked, m)?)?;
   m.add_function(wrap_pyfunction!(small_button_clicked, m)?)?;
+  m.add_function(wrap_pyfunction!(button, m)?)?;
+  m.add_function(wrap_pyfunction!(small_button, m)?)?;
   m.add_function(wrap_pyfunction!(horizontal, m)?)?;
   m.add_function(wrap_pyfunction!(horizontal_centered, m)?)?;
   m.add_function(wrap_pyfunction!(horizontal_top, m)?)?;
   m.add_function(wrap_pyfunction!(horizontal_wrapped, m)?)?;
+  m.add_function(wrap_pyfunction!(vertical_centered, m)?)?;
+  m.add_function(wrap_pyfunction!(vertical_centered_justified, m)?)?;
+  m.add_function(wrap_pyfunction!(with_layout, m)?)?;
+  m.add_function(wrap_pyfunction!(centered_and_justified, m)?)?;
   m.add_function(wrap_pyfunction!(collapsing, m)?)?;
   m.add_function(wrap_pyfunction!(indent, m)?)?;
   m.add_function(wrap_pyfunction!(group, m)?)?;
+  m.add_function(wrap_pyfunction!(frame, m)?)?;
+  m.add_function(wrap_pyfunction!(push_id, m)?)?;
+  m.add_function(wrap_pyfunction!(add_sized, m)?)?;
   m.add_function(wrap_pyfunction!(scope, m)?)?;
+  m.add_function(wrap_pyfunction!(side_panel_left, m)?)?;
+  m.add_function(wrap_pyfunction!(side_panel_right, m)?)?;
+  m.add_function(wrap_pyfunction!(top_panel, m)?)?;
+  m.add_function(wrap_pyfunction!(bottom_panel, m)?)?;
+  m.add_function(wrap_pyfunction!(scroll_area, m)?)?;
+  m.add_function(wrap_pyfunction!(scene, m)?)?;
+  m.add_function(wrap_pyfunction!(grid, m)?)?;
+  m.add_function(wrap_pyfunction!(end_row, m)?)?;
+  m.add_function(wrap_pyfunction!(table, m)?)?;
+  m.add_function(wrap_pyfunction!(dataframe, m)?)?;
+  m.add_function(wrap_pyfunction!(plot, m)?)?;
+  m.add_function(wrap_pyfunction!(menu_bar, m)?)?;
+  m.add_function(wrap_pyfunction!(menu_button, m)?)?;
+  m.add_function(wrap_pyfunction!(close_menu, m)?)?;
+  m.add_function(wrap_pyfunction!(popup_below_widget, m)?)?;
+  m.add_function(wrap_pyfunction!(toast_info, m)?)?;
+  m.add_function(wrap_pyfunction!(toast_success, m)?)?;
+  m.add_function(wrap_pyfunction!(toast_error, m)?)?;
+  m.add_function(wrap_pyfunction!(open_popup, m)?)?;
+  m.add_function(wrap_pyfunction!(close_popup, m)?)?;
+  m.add_function(wrap_pyfunction!(toggle_popup, m)?)?;
+  m.add_function(wrap_pyfunction!(is_popup_open, m)?)?;
   m.add_function(wrap_pyfunction!(slider_float, m)?)?;
   m.add_function(wrap_pyfunction!(slider_int, m)?)?;
   m.add_function(wrap_pyfunction!(drag_int, m)?)?;
@@ -1203,23 +8195,59 @@ fn pyegui(m: &Bound<'_, PyModule>) -> PyResult<()> {
   m.add_function(wrap_pyfunction!(hyperlink, m)?)?;
   m.add_function(wrap_pyfunction!(hyperlink_to, m)?)?;
   m.add_function(wrap_pyfunction!(link_clicked, m)?)?;
+  m.add_function(wrap_pyfunction!(link, m)?)?;
   m.add_function(wrap_pyfunction!(checkbox, m)?)?;
   m.add_function(wrap_pyfunction!(radio_value, m)?)?;
   m.add_function(wrap_pyfunction!(toggle_value, m)?)?;
   m.add_function(wrap_pyfunction!(selectable_value, m)?)?;
   m.add_function(wrap_pyfunction!(combo_box, m)?)?;
+  m.add_function(wrap_pyfunction!(combo_box_str, m)?)?;
+  m.add_function(wrap_pyfunction!(combo_box_search, m)?)?;
   m.add_function(wrap_pyfunction!(progress, m)?)?;
   m.add_function(wrap_pyfunction!(spinner, m)?)?;
   m.add_function(wrap_pyfunction!(color_edit_button_rgb, m)?)?;
+  m.add_function(wrap_pyfunction!(color_edit_button_rgba, m)?)?;
+  m.add_function(wrap_pyfunction!(color_edit_button_srgb, m)?)?;
+  m.add_function(wrap_pyfunction!(color_edit_button_hsva, m)?)?;
   m.add_function(wrap_pyfunction!(crate::image, m)?)?;
+  m.add_function(wrap_pyfunction!(image_clicked, m)?)?;
+  m.add_function(wrap_pyfunction!(image_hovered, m)?)?;
+  m.add_function(wrap_pyfunction!(image_dragged, m)?)?;
+  m.add_function(wrap_pyfunction!(image_from_bytes, m)?)?;
+  m.add_function(wrap_pyfunction!(image_from_array, m)?)?;
+  m.add_function(wrap_pyfunction!(matplotlib_figure, m)?)?;
+  m.add_function(wrap_pyfunction!(load_texture, m)?)?;
+  m.add_function(wrap_pyfunction!(textured_image, m)?)?;
   m.add_function(wrap_pyfunction!(image_and_text_clicked, m)?)?;
+  m.add_function(wrap_pyfunction!(image_and_text, m)?)?;
+  m.add_function(wrap_pyfunction!(image_button, m)?)?;
+  m.add_function(wrap_pyfunction!(image_toggle_value, m)?)?;
   m.add_function(wrap_pyfunction!(separator, m)?)?;
+  m.add_function(wrap_pyfunction!(separator_vertical, m)?)?;
+  m.add_function(wrap_pyfunction!(painter, m)?)?;
+  m.add_function(wrap_pyfunction!(allocate_response, m)?)?;
+  m.add_function(wrap_pyfunction!(dnd_drag_source, m)?)?;
+  m.add_function(wrap_pyfunction!(dnd_drop_zone, m)?)?;
   m.add_function(wrap_pyfunction!(set_invisible, m)?)?;
   m.add_function(wrap_pyfunction!(disable, m)?)?;
   m.add_function(wrap_pyfunction!(add_enabled, m)?)?;
   m.add_function(wrap_pyfunction!(set_opacity, m)?)?;
   m.add_function(wrap_pyfunction!(date_picker_button, m)?)?;
+  m.add_function(wrap_pyfunction!(date_range_picker, m)?)?;
+  m.add_function(wrap_pyfunction!(time_picker, m)?)?;
+  m.add_function(wrap_pyfunction!(datetime_picker, m)?)?;
   m.add_function(wrap_pyfunction!(add_space, m)?)?;
+  m.add_function(wrap_pyfunction!(set_width, m)?)?;
+  m.add_function(wrap_pyfunction!(set_height, m)?)?;
+  m.add_function(wrap_pyfunction!(set_min_width, m)?)?;
+  m.add_function(wrap_pyfunction!(set_min_height, m)?)?;
+  m.add_function(wrap_pyfunction!(set_max_width, m)?)?;
+  m.add_function(wrap_pyfunction!(set_max_height, m)?)?;
+  m.add_function(wrap_pyfunction!(set_min_size, m)?)?;
+  m.add_function(wrap_pyfunction!(set_max_size, m)?)?;
+  m.add_function(wrap_pyfunction!(available_width, m)?)?;
+  m.add_function(wrap_pyfunction!(available_height, m)?)?;
+  m.add_function(wrap_pyfunction!(available_size, m)?)?;
   Ok(())
 }
 
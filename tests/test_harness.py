@@ -0,0 +1,83 @@
+import os
+
+import pytest
+
+from pyegui import Int, Str, button_clicked, label, text_edit_singleline
+from pyegui.testing import Harness
+
+
+def test_click():
+    count = Int(0)
+
+    def update_fun(ctx):
+        label(f"Count: {count.value}")
+        if button_clicked("Increment"):
+            count.value += 1
+
+    h = Harness(update_fun)
+    h.click("Increment")
+
+    assert count.value == 1
+    assert h.has_label("Count: 1")
+
+
+def test_click_unknown_label():
+    h = Harness(lambda ctx: label("hello"))
+
+    with pytest.raises(LookupError):
+        h.click("Increment")
+
+
+def test_type_text():
+    text = Str("")
+
+    def update_fun(ctx):
+        text_edit_singleline(text)
+        label(f"Echo: {text.value}")
+
+    h = Harness(update_fun)
+    h.type_text("hello")
+
+    assert text.value == "hello"
+    assert h.has_label("Echo: hello")
+
+
+def test_press_key():
+    pressed = Int(0)
+
+    def update_fun(ctx):
+        if ctx.key_pressed("Enter"):
+            pressed.value += 1
+
+    h = Harness(update_fun)
+    h.press_key("Enter")
+
+    assert pressed.value == 1
+
+
+def test_exception_is_raised():
+    def update_fun(ctx):
+        if button_clicked("Fail"):
+            raise ValueError("broken")
+
+    h = Harness(update_fun)
+
+    with pytest.raises(ValueError, match="broken"):
+        h.click("Fail")
+
+
+def test_exception_in_first_frame_is_raised():
+    def update_fun(ctx):
+        raise KeyError("first frame")
+
+    with pytest.raises(KeyError):
+        Harness(update_fun)
+
+
+def test_snapshot():
+    def update_fun(ctx):
+        label("Snapshot")
+        button_clicked("Button")
+
+    h = Harness(update_fun, width=200, height=100)
+    h.snapshot("label_and_button", output_path=os.path.join(os.path.dirname(__file__), "snapshots"))